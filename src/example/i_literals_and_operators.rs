@@ -29,3 +29,56 @@ fn one() {
     // 使用下划线改善数字的可读性
     println!("one million is written as {}", 1_000_000u32);
 }
+
+#[test]
+fn two() {
+    use crate::toolkit::flags::{flag_set, Flags};
+
+    // 给 `one` 里的每一位起个名字，位运算就变成了集合运算。
+    flag_set! {
+        struct Nibble: u32 {
+            const A = 0b0001;
+            const B = 0b0010;
+            const C = 0b0100;
+            const D = 0b1000;
+        }
+    }
+
+    let x = Nibble::A | Nibble::B; // 0011
+    let y = Nibble::A | Nibble::C; // 0101
+
+    println!("{} AND {} is {} ({:04b})", x, y, x & y, x & y);
+    println!("{} OR {} is {} ({:04b})", x, y, x | y, x | y);
+    println!("{} XOR {} is {} ({:04b})", x, y, x ^ y, x ^ y);
+    println!("{} minus {} is {}", x, y, x - y);
+    println!("NOT {} is {}", x, !x);
+
+    for (name, flag) in (x | y).iter() {
+        println!("{} = {:04b}", name, flag);
+    }
+    println!("{:?}", "D | B".parse::<Nibble>());
+}
+
+#[test]
+fn three() {
+    use crate::toolkit::bitset::{lowest_one, BitSet};
+
+    // `one` 里的移位和按位与只能处理一个字，`BitSet` 把多个 `u64` 拼成一个大的位集合。
+    println!("lowest one of 0b1011_0000 is {:b}", lowest_one(0b1011_0000));
+
+    let mut primes: BitSet<2> = BitSet::new();
+    for p in [2, 3, 5, 7, 11, 13, 61, 67, 71, 127] {
+        primes.insert(p);
+    }
+    println!("primes below 128: {:?}", primes);
+    println!("there are {} of them", primes.len());
+    println!("{} primes are below 64", primes.rank(64));
+    println!("the 7th prime is {:?}", primes.select(6));
+    println!(
+        "primes in 60..100: {:?}",
+        primes.iter_range(60..100).collect::<Vec<_>>()
+    );
+
+    let odd: BitSet<2> = (1..128).step_by(2).collect();
+    println!("even primes: {:?}", primes - odd);
+}
@@ -6,6 +6,15 @@ use std::fmt::{Display, Formatter};
  * @Date: create in 2021/9/6 23:25
  */
 
+#[derive(Debug)]
+pub(crate) struct Matrix(pub(crate) f32, pub(crate) f32, pub(crate) f32, pub(crate) f32);
+
+impl Display for Matrix {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({} {})\n({} {})", self.0, self.1, self.2, self.3)
+    }
+}
+
 #[test]
 fn one() {
     fn reverse(pair: (i32, bool)) -> (bool, i32) {
@@ -14,15 +23,6 @@ fn one() {
         (boolean, integer)
     }
 
-    #[derive(Debug)]
-    struct Matrix(f32, f32, f32, f32);
-
-    impl Display for Matrix {
-        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            write!(f, "({} {})\n({} {})", self.0, self.1, self.2, self.3)
-        }
-    }
-
     // 包含各种不同类型的元组
     let long_tuple = (
         1u8, 2u16, 3u32, 4u64, -1i8, -2i16, -3i32, -4i64, 0.1f32, 0.2f64, 'a', true,
@@ -63,3 +63,17 @@ fn one() {
 
     println!("Transpose:\n{}", transpose(matrix));
 }
+
+#[test]
+fn two() {
+    use crate::toolkit::tuple_ext::{PopFront, PushBack, TupleReverse};
+
+    // `one` 里的 `reverse` 只能处理 `(i32, bool)`，这里对任意 1 到 12 元的元组都可以用。
+    let pair = (1, true);
+    println!("the reversed pair is {:?}", pair.reverse());
+
+    let tuple = (1, "hello", 4.5, true);
+    println!("reversed: {:?}", tuple.reverse());
+    println!("pushed: {:?}", tuple.push_back('x'));
+    println!("popped: {:?}", tuple.pop_front());
+}
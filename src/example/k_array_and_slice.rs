@@ -48,3 +48,27 @@ fn one() {
     // 越界的下标会引发致命错误（panic）
     // println!("{}", xs[5]);
 }
+
+#[test]
+fn two() {
+    use crate::toolkit::slice_stats::SliceStats;
+
+    // 不会 panic 的 `analyze_slice`：空切片时返回 `Err(StatsError::Empty)`。
+    fn analyze_slice(slice: &[i32]) {
+        match slice.summary() {
+            Ok(summary) => println!("{:?}", summary),
+            Err(e) => println!("cannot analyze: {}", e),
+        }
+    }
+
+    let xs = [1, 2, 3, 4, 5];
+    let ys = [0; 500];
+
+    analyze_slice(&xs);
+    analyze_slice(&ys[1..4]);
+    analyze_slice(&xs[..0]);
+
+    println!("first element of the array: {:?}", xs.first());
+    println!("90th percentile: {:?}", xs.percentile(90.0));
+    print!("{}", xs.histogram(5).unwrap());
+}
@@ -8,31 +8,31 @@
 */
 
 #[derive(Debug)]
-struct Person<'a> {
-    name: &'a str,
-    age: u8,
+pub(crate) struct Person<'a> {
+    pub(crate) name: &'a str,
+    pub(crate) age: u8,
 }
 
 // 单元结构体
 struct Nil;
 
 // 元组结构体
-struct Pair(i32, f32);
+pub(crate) struct Pair(pub(crate) i32, pub(crate) f32);
 
 // 带两个字段的接口体
 #[allow(dead_code)]
 #[derive(Debug)]
-struct Point {
-    x: f32,
-    y: f32,
+pub(crate) struct Point {
+    pub(crate) x: f32,
+    pub(crate) y: f32,
 }
 
 // 结构体可以作为另一个结构体的参数
 #[allow(dead_code)]
 #[derive(Debug)]
-struct Rectangle {
-    p1: Point,
-    p2: Point,
+pub(crate) struct Rectangle {
+    pub(crate) p1: Point,
+    pub(crate) p2: Point,
 }
 
 #[test]
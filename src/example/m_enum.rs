@@ -4,7 +4,7 @@
  * @Date: create in 2021/9/9 23:41
  */
 
-enum WebEvent {
+pub(crate) enum WebEvent {
     // 单元结构体 称为unit-like or unit
     PageLoad,
     PageUnload,
@@ -7,19 +7,19 @@ mod f_display_list;
 mod g_formatting;
 mod h_primitives;
 mod i_literals_and_operators;
-mod j_tuple;
+pub(crate) mod j_tuple;
 mod k_array_and_slice;
-mod l_struct;
-mod m_enum;
-mod n_use;
-mod o_c_language_style;
-mod p_linked_list;
-mod q_constants;
+pub(crate) mod l_struct;
+pub(crate) mod m_enum;
+pub(crate) mod n_use;
+pub(crate) mod o_c_language_style;
+pub(crate) mod p_linked_list;
+pub(crate) mod q_constants;
 mod r_casting;
 mod s_from_and_into;
 mod t_expressions;
 mod u_flow_of_control;
-mod v_for_and_iter;
+pub(crate) mod v_for_and_iter;
 mod w_match;
 mod x_destructuring;
 mod y_if_let;
@@ -31,7 +31,7 @@ mod zd_closure_capture;
 mod ze_closure_input_params;
 mod zf_closure_type_anonymity;
 mod zg_closure_example;
-mod zh_hof;
+pub(crate) mod zh_hof;
 mod zi_diverging_function;
 mod zj_module;
 mod zk_file_hierarchy;
@@ -4,15 +4,22 @@
  * @Date: create in 2021/9/13 4:08 下午
  */
 
-#[allow(dead_code)]
-enum Status {
-    Rich,
-    Poor,
+use crate::toolkit::enum_reflect::reflect_enum;
+
+reflect_enum! {
+    #[allow(dead_code)]
+    pub(crate) enum Status {
+        Rich,
+        Poor,
+    }
 }
-#[allow(dead_code)]
-enum Work {
-    Civilian,
-    Soldier,
+
+reflect_enum! {
+    #[allow(dead_code)]
+    pub(crate) enum Work {
+        Civilian,
+        Soldier,
+    }
 }
 
 #[test]
@@ -39,3 +46,21 @@ fn one() {
         Soldier => println!("Soldiers fight!"),
     }
 }
+
+#[test]
+fn two() {
+    use crate::toolkit::rules::{Facts, RuleSet, Schema};
+
+    // `one` 里的两个 match 写在 `n_use.rules` 里，由规则引擎求值。
+    let schema = Schema::new().with_enum::<Status>().with_enum::<Work>();
+    let rules = RuleSet::parse(schema, include_str!("n_use.rules")).unwrap();
+
+    let facts = Facts::new().with(Status::Poor).with(Work::Civilian);
+    for firing in rules.evaluate(&facts) {
+        println!("{}", firing.message);
+    }
+
+    for diagnostic in rules.analyze() {
+        println!("warning: {}", diagnostic);
+    }
+}
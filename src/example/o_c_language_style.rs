@@ -1,18 +1,24 @@
 // 该属性用于隐藏对未使用代码的警告。
 #![allow(dead_code)]
 
-// 拥有隐式辨别值（implicit discriminator，从 0 开始）的 enum
-enum Number {
-    Zero,
-    One,
-    Two,
+use crate::toolkit::enum_reflect::reflect_enum;
+
+reflect_enum! {
+    // 拥有隐式辨别值（implicit discriminator，从 0 开始）的 enum
+    pub(crate) enum Number {
+        Zero,
+        One,
+        Two,
+    }
 }
 
-// 拥有显式辨别值（explicit discriminator）的 enum
-enum Color {
-    Red = 0xff0000,
-    Green = 0x00ff00,
-    Blue = 0x0000ff,
+reflect_enum! {
+    // 拥有显式辨别值（explicit discriminator）的 enum
+    pub(crate) enum Color {
+        Red = 0xff0000,
+        Green = 0x00ff00,
+        Blue = 0x0000ff,
+    }
 }
 
 #[test]
@@ -24,3 +30,17 @@ fn one() {
     println!("roses are #{:06x}", Color::Red as i32);
     println!("violets are #{:06x}", Color::Blue as i32);
 }
+
+#[test]
+fn two() {
+    use crate::toolkit::enum_reflect::EnumReflect;
+    use std::convert::TryFrom;
+
+    // `reflect_enum!` 让 C 风格的枚举还能反过来从名字或整数得到变体。
+    for color in Color::iter() {
+        println!("{} is #{:06x}", color, color.discriminant());
+    }
+    println!("{:?}", "Green".parse::<Color>());
+    println!("{:?}", Number::try_from(2));
+    println!("{:?}", Number::try_from(3));
+}
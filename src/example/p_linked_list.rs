@@ -6,7 +6,7 @@
 
 use List::*;
 
-enum List {
+pub(crate) enum List {
     // Cons：元组结构体，包含链表的一个元素和一个指向下一节点的指针
     Cons(u32, Box<List>),
     // Nil：末结点，表明链表结束
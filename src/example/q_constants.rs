@@ -4,11 +4,12 @@
  * @Date: create in 2021/9/13 4:49 下午
  */
 
-static LANGUAGE: &'static str = "RUST";
-const THRESHOLD: i32 = 10;
+// 这两个值现在是 `toolkit::config` 的编译期默认值，运行时可以被覆盖。
+pub(crate) static LANGUAGE: &'static str = "RUST";
+pub(crate) const THRESHOLD: i32 = 10;
 
 fn is_big(n: i32) -> bool {
-    n > THRESHOLD
+    n > crate::toolkit::config::global().threshold()
 }
 
 #[test]
@@ -24,3 +25,19 @@ fn one() {
     // THRESHOLD = 5;
     // 改正 ^ 注释掉此行
 }
+
+#[test]
+fn two() {
+    use crate::toolkit::config::{Config, Layer};
+
+    // 常量只是默认值，配置可以在运行时覆盖它们。
+    let mut config = Config::default();
+    println!("This is {}", config.language());
+    println!("The threshold is {}", config.threshold());
+
+    config
+        .set("threshold", "20", Layer::Cli, "--set threshold")
+        .unwrap();
+    println!("{}", config.dump());
+    println!("16 is {}", if 16 > config.threshold() { "big" } else { "small" });
+}
@@ -103,4 +103,21 @@ fn four() {
              inches,
              nanoseconds + inches);
 
-}
\ No newline at end of file
+}
+
+// 用 `toolkit::checked_cast` 解释 `one` 里那些出人意料的结果。
+#[test]
+fn five() {
+    use crate::toolkit::checked_cast::{checked_cast, explain, saturating_cast};
+
+    println!("{}", explain::<_, u8>(1000i32));
+    println!("{}", explain::<_, u8>(-1i8));
+    println!("{}", explain::<_, i8>(128i32));
+    println!("{}", explain::<_, i8>(232u8));
+    println!("{}", explain::<_, u8>(65.4321_f32));
+    println!("{}", explain::<_, char>(65u8));
+
+    // 不想要 `as` 的语义时，可以明确选择失败或者饱和。
+    println!("checked 1000 -> u8: {:?}", checked_cast::<i32, u8>(1000));
+    println!("saturating 1000 -> u8: {}", saturating_cast::<i32, u8>(1000));
+}
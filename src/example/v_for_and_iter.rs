@@ -43,4 +43,62 @@ fn third() {
         }
     }
     println!("names: {:?}", names);
-}
\ No newline at end of file
+}
+
+// 基准测试：每个变体都先建一份同样的名单，再用不同的方式遍历，
+// 把打招呼的话的长度加起来作为校验值。
+use crate::toolkit::bench::{Group, Variant};
+
+fn bench_names() -> Vec<&'static str> {
+    ["Bob", "Frank", "Ferris"].iter().copied().cycle().take(3_000).collect()
+}
+
+fn greeting(name: &str) -> &'static str {
+    match name {
+        "Ferris" => "There is a rustacean among us!",
+        _ => "Hello",
+    }
+}
+
+fn with_iter() -> u64 {
+    let names = bench_names();
+    let total = names.iter().map(|name| greeting(name).len() as u64).sum();
+    // `iter` 只是借用，`names` 还能用。
+    std::hint::black_box(&names);
+    total
+}
+
+fn with_into_iter() -> u64 {
+    let names = bench_names();
+    names.into_iter().map(|name| greeting(name).len() as u64).sum()
+}
+
+fn with_iter_mut() -> u64 {
+    let mut names = bench_names();
+    for name in names.iter_mut() {
+        *name = greeting(name);
+    }
+    names.iter().map(|s| s.len() as u64).sum()
+}
+
+/// 本章登记的基准测试，见 `toolkit::bench`。
+pub(crate) fn benches() -> Vec<Group> {
+    vec![Group {
+        chapter: "v_for_and_iter",
+        name: "iter vs into_iter vs iter_mut",
+        variants: vec![
+            Variant {
+                name: "iter",
+                run: with_iter,
+            },
+            Variant {
+                name: "into_iter",
+                run: with_into_iter,
+            },
+            Variant {
+                name: "iter_mut",
+                run: with_iter_mut,
+            },
+        ],
+    }]
+}
@@ -25,3 +25,23 @@ fn one() {
 
     println!("{}->{}", boolean, binary);
 }
+
+#[test]
+fn two() {
+    use crate::toolkit::number_theory::{classify, Primality};
+
+    // `one` 里写死的素数表和 `13..=19` 范围，可以换成 `classify` 返回的结构化结果。
+    for number in &[1, 13, 28, 97, 360] {
+        let c = classify(*number);
+
+        match c.primality {
+            Primality::One => println!("{}: one", number),
+            Primality::Prime if c.teen => println!("{}: a teen prime", number),
+            Primality::Prime => println!("{}: this is a prime", number),
+            Primality::Composite(ref factors) => {
+                println!("{}: composite {:?}, {:?}", number, factors, c.abundance)
+            }
+            Primality::Zero => println!("zero"),
+        }
+    }
+}
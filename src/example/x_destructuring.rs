@@ -189,3 +189,29 @@ fn seventh() {
         _ => (),
     }
 }
+
+// 动态数据
+#[test]
+fn eighth() {
+    use crate::toolkit::value::{destructure, Value, ValueError};
+
+    // 结构在运行时才知道，类型不对时得到一个说明位置的错误，而不是编译错误。
+    fn describe(foo: &Value) -> Result<String, ValueError> {
+        // 和 `fourth` 里的 `let Foo { x: (a, b), y } = foo;` 对应。
+        destructure!(let { x: { a: u32, b: u32 }, y: u32 } = foo);
+        Ok(format!("a = {}, b = {}, y = {}", a, b, y))
+    }
+
+    let foo = Value::map(vec![
+        ("x", Value::map(vec![("a", 1), ("b", 2)])),
+        ("y", Value::from(3)),
+    ]);
+    println!("{}", foo);
+    println!("{:?}", describe(&foo));
+
+    let bar = Value::map(vec![("x", Value::from("oops")), ("y", Value::from(3))]);
+    match describe(&bar) {
+        Ok(text) => println!("{}", text),
+        Err(e) => println!("error: {}", e),
+    }
+}
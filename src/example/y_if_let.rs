@@ -74,3 +74,41 @@ fn one() {
         println!("c is {}", value);
     }
 }
+
+#[test]
+fn two() {
+    use crate::toolkit::combinators::{variant_of, OptionExt, ResultExt};
+
+    // `one` 里的 `if let ... else` 链也可以写成组合子。
+    // `inspect` 是标准库的，`inspect_none` 来自 `OptionExt`。
+    let number = Some(7);
+    let letter: Option<i32> = None;
+
+    let a = number
+        .inspect(|i| println!("Matched {:?}", i))
+        .inspect_none(|| println!("asd"))
+        .unwrap_or(0);
+    println!("{}", a);
+
+    letter.inspect_none(|| println!("Didn't match a number. let's go with a letter"));
+    println!("{:?}", number.zip_map(Some(3), |a, b| a * b));
+
+    // 解析失败时打印原因并丢掉这个值。
+    let parsed = "seven".parse::<i32>().ok_or_log("parsing `seven`");
+    println!("{:?}", parsed);
+
+    enum Foo {
+        Bar,
+        Qux(u32),
+    }
+
+    let a = Foo::Bar;
+    let c = Foo::Qux(100);
+
+    if variant_of!(a, Foo::Bar) {
+        println!("a is foobar");
+    }
+    if let Some(value) = variant_of!(c, Foo::Qux(value) => value) {
+        println!("c is {}", value);
+    }
+}
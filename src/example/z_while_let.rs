@@ -45,3 +45,37 @@ fn two() {
     // ^ `if let` 有可选的 `else`/`else if` 分句，
     // 而 `while let` 没有。
 }
+
+#[test]
+fn three() {
+    use crate::toolkit::retry::{Backoff, FakeClock, Retry, Stopped};
+    use std::time::Duration;
+
+    // 把 `two` 里的循环交给 `Retry`：闭包返回 `None` 就再试一次。
+    let mut optional: Option<i32> = Some(0);
+    let clock = FakeClock::new();
+    let retry = Retry::new().max_attempts(20).backoff(Backoff::Linear {
+        initial: Duration::from_millis(10),
+        step: Duration::from_millis(10),
+    });
+
+    let report = retry.run(&clock, |attempt| {
+        let i = optional?;
+        if i > 9 {
+            println!("Greater than 9, quit!");
+            Some(attempt)
+        } else {
+            println!("`i` is `{:?}`. Try again.", i);
+            optional = Some(i + 1);
+            None
+        }
+    });
+
+    match report.result {
+        Ok(attempt) => println!("done after {} attempts", attempt),
+        Err(Stopped::Exhausted(())) => println!("gave up"),
+        Err(stopped) => println!("stopped: {:?}", stopped),
+    }
+    // 假时钟没有真的睡眠，只是记下了应该等多久。
+    println!("waited {:?} in total", report.elapsed);
+}
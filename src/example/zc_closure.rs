@@ -39,4 +39,35 @@ fn one() {
     let one = || 1;
     println!("closure returning one: {}", one());
 
-}
\ No newline at end of file
+}
+
+#[test]
+fn two() {
+    use crate::toolkit::memo::{memoize, memoize_rec};
+
+    fn function(i: i32) -> i32 {
+        i + 1
+    }
+
+    // 函数和闭包都是 `Fn(i32) -> i32`，都可以加上缓存。
+    let cached_function = memoize(function);
+    let cached_closure = memoize(|i: i32| i + 1);
+    for i in [1, 2, 1, 1] {
+        println!(
+            "function: {}, closure: {}",
+            cached_function.call(i),
+            cached_closure.call(i)
+        );
+    }
+    println!("{:?}", cached_function.stats());
+
+    // 递归的闭包通过第一个参数调用自己。
+    let fib = memoize_rec(|fib, n: u64| {
+        if n < 2 {
+            n
+        } else {
+            fib(n - 1) + fib(n - 2)
+        }
+    });
+    println!("fib(80) = {}, {:?}", fib.call(80), fib.stats());
+}
@@ -71,3 +71,42 @@ fn one() {
     // `haystack` 仍然可用，取消上面的注释也不会导致错误。
 
 }
+
+#[test]
+fn two() {
+    use crate::example::m_enum::WebEvent;
+    use crate::toolkit::callbacks::Registry;
+
+    let color = "green";
+    let mut count = 0;
+    let movable = Box::new(3);
+    let haystack = "abc".to_owned();
+
+    {
+        let mut registry = Registry::new();
+        // 通过引用捕获 `color`。
+        registry.register(|_: &WebEvent| println!("color :{}", color));
+        // 通过可变引用捕获 `count`。
+        registry.register(|_| {
+            count += 1;
+            println!("count {}", count);
+        });
+        // 通过值捕获 `movable`，只能调用一次。
+        registry.once(|_| {
+            println!("movable :{:?}", movable);
+            std::mem::drop(movable)
+        });
+        // `move` 把 `haystack` 移进闭包。
+        registry.register(move |event| {
+            if let WebEvent::KeyPress(c) = event {
+                println!("{} in haystack: {}", c, haystack.contains(*c));
+            }
+        });
+
+        println!("called {} handlers", registry.emit(&WebEvent::KeyPress('a')));
+        println!("called {} handlers", registry.emit(&WebEvent::KeyPress('z')));
+    }
+
+    // 注册表没了，对 `count` 的可变借用也就结束了。
+    println!("count after: {}", count);
+}
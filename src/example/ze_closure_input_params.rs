@@ -56,3 +56,39 @@ fn one() {
 
     println!("3 double {}", apply_to_3(double));
 }
+
+#[test]
+fn two() {
+    use crate::toolkit::pipeline::{Pipeline, Stage};
+
+    // 把几个闭包串成一条流水线：`Fn` 和 `FnMut` 都可以作为其中一步。
+    let limit = 100;
+    let mut doubled = 0;
+
+    let mut pipeline = Pipeline::new()
+        .map(|x: i32| 2 * x)
+        .then(|x| {
+            doubled += 1;
+            if x <= limit {
+                Ok(x)
+            } else {
+                Err(format!("{} is over the limit", x))
+            }
+        })
+        .around(|x, next| {
+            println!("-> {}", x);
+            let result = next.run(x);
+            println!("<- {:?}", result);
+            result
+        });
+
+    println!("3 double {:?}", pipeline.run(3));
+    println!("60 double {:?}", pipeline.run(60));
+    println!("doubled {} times", doubled);
+
+    // 绑定输入之后就是一个 `FnOnce()`，可以交给 `one` 里那样的 `apply`。
+    let greet = Pipeline::<_, (), _>::new()
+        .map(|s: String| s + "!!!")
+        .bind("hello".to_owned());
+    println!("{:?}", greet());
+}
@@ -63,3 +63,24 @@ fn third() {
     fn_mut();
     fn_once();
 }
+
+#[test]
+fn fourth() {
+    use crate::toolkit::lazy::Lazy;
+
+    fn create_fn_once() -> impl FnOnce() -> String {
+        let text = "FnOnce".to_owned();
+        move || {
+            println!("computing...");
+            format!("This is a: {}", text)
+        }
+    }
+
+    // `FnOnce` 只能调用一次；交给 `Lazy` 之后可以反复读取结果，
+    // 而且第一次读取时才会真正调用。
+    let lazy = Lazy::new(create_fn_once());
+    println!("created: {:?}", lazy);
+    println!("{}", *lazy);
+    println!("{}", *lazy);
+    println!("after: {:?}", lazy);
+}
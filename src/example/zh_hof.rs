@@ -39,3 +39,104 @@ fn one() {
             .fold(0, |sum, i| sum + i); // 最后加起来
     println!("functional style: {}", sum_of_squared_odd_numbers);
 }
+
+#[test]
+fn two() {
+    use crate::toolkit::iter_ext::IterExt;
+
+    let upper = 1000;
+
+    // `scan_while` 给出累加的过程：平方数的前缀和，直到超过上限。
+    let running: Vec<u32> = (0..)
+        .map(|n| n * n)
+        .filter(|n| n % 2 == 1)
+        .scan_while(0, |sum, n| sum + n, |&sum| sum < upper)
+        .collect();
+    println!("running sums: {:?}", running);
+
+    // 按十位分组，再把相邻的平方数两两配对。
+    for (tens, group) in (0..).map(|n| n * n).take_while(|&n| n < 100).group_by_key(|n| n / 10) {
+        println!("{}x: {:?}", tens, group);
+    }
+    let pairs: Vec<Vec<u32>> = (1..6).map(|n| n * n).windows(2).collect();
+    println!("neighbours: {:?}", pairs);
+
+    // 合并奇数的平方和偶数的平方，结果仍然有序。
+    let odd = (0..).map(|n| 2 * n + 1).map(|n| n * n);
+    let even = (0..).map(|n| 2 * n).map(|n| n * n);
+    let merged: Vec<u32> = odd.merge_sorted(even).take(8).collect();
+    println!("merged squares: {:?}", merged);
+}
+
+#[test]
+fn three() {
+    use crate::toolkit::bench::{run_group, Options};
+    use crate::toolkit::retry::FakeClock;
+    use std::time::Duration;
+
+    // 两种写法不仅结果相同，速度也可以比一比。
+    println!("imperative style: {}", imperative(1000));
+    println!("functional style: {}", functional(1000));
+
+    // 测试里用假的时钟，只看报告的格式；真正的计时用 `cargo run --release -- bench zh_hof`。
+    let options = Options {
+        min_sample_time: Duration::ZERO,
+        ..Options::quick()
+    };
+    for group in benches() {
+        let report = run_group(&FakeClock::new(), &group, &options).unwrap();
+        assert_eq!(report.results.len(), 2);
+        println!("{}", report);
+    }
+}
+
+// 下面是本章的基准测试，`three` 和 `toolkit::bench` 会用到。
+use crate::toolkit::bench::{Group, Variant};
+use std::hint::black_box;
+
+// 基准测试用的上限，比 `one` 里的 1000 大得多，差别才测得出来。
+const BENCH_UPPER: u64 = 10_000_000;
+
+fn is_odd(n: u64) -> bool {
+    n % 2 == 1
+}
+
+// 和 `one` 里的两种写法相同，只是换成了 `u64`。
+pub(crate) fn imperative(upper: u64) -> u64 {
+    let mut acc = 0;
+    for n in 0.. {
+        let n_squared = n * n;
+        if n_squared >= upper {
+            break;
+        } else if is_odd(n_squared) {
+            acc += n_squared;
+        }
+    }
+    acc
+}
+
+pub(crate) fn functional(upper: u64) -> u64 {
+    (0..)
+        .map(|n| n * n)
+        .take_while(|&n| n < upper)
+        .filter(|&n| is_odd(n))
+        .sum()
+}
+
+/// 本章登记的基准测试，见 `toolkit::bench`。
+pub(crate) fn benches() -> Vec<Group> {
+    vec![Group {
+        chapter: "zh_hof",
+        name: "sum of squared odd numbers",
+        variants: vec![
+            Variant {
+                name: "imperative",
+                run: || imperative(black_box(BENCH_UPPER)),
+            },
+            Variant {
+                name: "functional",
+                run: || functional(black_box(BENCH_UPPER)),
+            },
+        ],
+    }]
+}
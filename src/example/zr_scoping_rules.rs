@@ -378,3 +378,24 @@ fn eighth() {
     }
 }
 
+// 作用域守卫
+//
+// `one` 里的 `ToDrop` 只会在销毁时打印一句话。`ScopeGuard` 把任意闭包挂到销毁上，
+// 可以只在正常返回或者只在 panic 时执行，也可以中途取消；`DropLog` 把销毁顺序记下来。
+#[test]
+fn ninth() {
+    use crate::toolkit::scope_guard::{self, defer, DropLog};
+
+    let log = DropLog::new();
+    {
+        defer! { log.record("deferred"); }
+        let _x = log.track("ToDrop");
+        let _done = scope_guard::guard_on_success((), |()| log.record("no panic"));
+        let cancelled = scope_guard::scope_exit(|| log.record("cancelled"));
+        cancelled.dismiss();
+        log.record("made a ToDrop");
+    }
+    for event in log.events() {
+        println!("{}", event);
+    }
+}
@@ -159,6 +159,8 @@ fn two() {
 // 则需要使用 dyn 关键字编写返回类型，例如 Box<dyn Animal>。
 #[test]
 fn third() {
+    use crate::toolkit::rng::{Pcg32, Rng};
+
     struct Sheep {}
     struct Cow {}
 
@@ -182,8 +184,8 @@ fn third() {
     }
 
     // 返回一些实现 Animal 的结构体，但是在编译时我们不知道哪个结构体。
-    fn random_animal(random_number: f64) -> Box<dyn Animal> {
-        if random_number < 0.5 {
+    fn random_animal(rng: &mut impl Rng) -> Box<dyn Animal> {
+        if rng.next_f64() < 0.5 {
             Box::new(Sheep {})
         } else {
             Box::new(Cow {})
@@ -191,12 +193,16 @@ fn third() {
     }
 
     {
-        let random_number = 0.234;
-        let animal = random_animal(random_number);
-        println!(
-            "You've randomly chosen an animal, and it says {}",
-            animal.noise()
-        );
+        // 固定的种子让每次运行的结果都一样；
+        // 换成 `Pcg32::new(seed_from_time())` 每次就不一样了。
+        let mut rng = Pcg32::new(234);
+        for _ in 0..3 {
+            let animal = random_animal(&mut rng);
+            println!(
+                "You've randomly chosen an animal, and it says {}",
+                animal.noise()
+            );
+        }
     }
 }
 
@@ -308,3 +314,113 @@ fn sixth() {
         // `_a` *不会*在这里再次销毁，因为它已经被（手动）销毁。
     }
 }
+
+// 带状态的动物
+//
+// `one` 里的 `Sheep` 只有一个 `naked` 标志。`toolkit::farm` 里的动物每过一天都会变饿，
+// 绵羊的毛会长回来，奶牛会产奶，母鸡会下蛋；不同种类的动物都放在同一个
+// `Vec<Box<dyn Animal>>` 里。
+#[test]
+fn seventh() {
+    use crate::toolkit::farm::{Animal, Chicken, Cow, Farm, Sheep};
+
+    let mut farm = Farm::new();
+    farm.add(Sheep::new("Dolly"));
+    farm.add(Cow::new("Bessie"));
+    farm.add(Chicken::new("Henny"));
+
+    for line in farm.chorus() {
+        println!("{}", line);
+    }
+
+    farm.shear("Dolly").unwrap();
+    farm.run(4);
+    farm.milk("Bessie").unwrap();
+    farm.feed_hungry(5);
+    if let Err(e) = farm.shear("Bessie") {
+        println!("error: {}", e);
+    }
+
+    for event in farm.log() {
+        println!("{}", event);
+    }
+    println!("{:?}", farm.produce());
+}
+
+// 按名字创建动物
+//
+// `third` 只能用 if/else 在两种动物之间选。把构造函数按名字登记到注册表里，
+// 就能从一行配置创建任意种类的 `Box<dyn Animal>`，也能随机挑一种。
+#[test]
+fn eighth() {
+    use crate::toolkit::animal_factory::AnimalRegistry;
+    use crate::toolkit::rng::{Pcg32, Rng};
+
+    let registry = AnimalRegistry::with_builtin();
+    for (kind, description) in registry.kinds() {
+        println!("{:<8} {}", kind, description);
+    }
+
+    for line in ["cow name=Bessie", "sheep name=Dolly naked=true", "horse name=Ed"] {
+        match registry.create(line) {
+            Ok(animal) => println!("{}", animal.talk()),
+            Err(e) => println!("error: {}", e),
+        }
+    }
+
+    let kinds: Vec<&str> = registry.kinds().map(|(kind, _)| kind).collect();
+    let mut rng = Pcg32::new(234);
+    let kind = rng.choose(&kinds).unwrap();
+    let animal = registry.create(&format!("{} name=Lucky", kind)).unwrap();
+    println!("You've randomly chosen a {}, and it says {}", kind, animal.noise());
+}
+
+// 符号运算
+//
+// `fifth` 里 `Foo + Bar` 的结果是另一个类型。如果结果是一棵表达式树，
+// 重载过的运算符就成了一门小语言：写出来的式子可以化简、代入、求导和求值。
+#[test]
+fn ninth() {
+    use crate::toolkit::symbolic::{Const, Var};
+
+    let x = Var::new("x");
+    let f = &x * &x + 3.0 * &x + Const(5.0);
+    let df = f.diff("x");
+    println!("f(x)  = {}", f);
+    println!("f'(x) = {}", df);
+    println!("f(2) = {:?}, f'(2) = {:?}", f.eval(&[("x", 2.0)]), df.eval(&[("x", 2.0)]));
+
+    let g = f.subst("x", &x - 1.0);
+    println!("f(x - 1) = {} = {}", g, g.simplify());
+}
+
+// 事务
+//
+// `sixth` 里的 `Droppable` 在销毁时打印。把回滚放进 `drop`，
+// 没有提交的事务不管是正常离开作用域还是因为 panic 离开，都不会改动存储。
+#[test]
+fn tenth() {
+    use crate::toolkit::transaction::Store;
+
+    let mut store = Store::new();
+    {
+        let mut tx = store.begin();
+        tx.put("a", 1);
+        tx.put("b", 2);
+        {
+            let mut savepoint = tx.savepoint();
+            savepoint.put("c", 3);
+            println!("Exiting the savepoint without commit");
+        }
+        tx.commit();
+    }
+    {
+        let mut tx = store.begin();
+        tx.delete("a");
+        println!("Exiting the transaction without commit");
+    }
+    for (key, value) in store.iter() {
+        println!("{} = {}", key, value);
+    }
+    println!("commits: {}, rollbacks: {}", store.commits(), store.rollbacks());
+}
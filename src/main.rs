@@ -1,5 +1,33 @@
 mod example;
+mod toolkit;
+
+use std::env;
+use std::process;
 
 fn main() {
-    println!("this is learn rust by example project");
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    if let Err(e) = run(&args) {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    }
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    // `--config FILE` 和 `--set key=value` 可以出现在任何位置。
+    let args = toolkit::config::init_from_args(args).map_err(|e| e.to_string())?;
+
+    match args.first().map(String::as_str) {
+        // `cargo run -- layout [Type...]`
+        Some("layout") => toolkit::layout::run(&args[1..]),
+        // `cargo run --release -- bench [--quick] [filter...]`
+        Some("bench") => toolkit::bench::run(&args[1..]),
+        // `cargo run -- config dump`
+        Some("config") => toolkit::config::run(&args[1..]),
+        Some(other) => Err(format!("unknown command `{}`", other)),
+        None => {
+            println!("this is learn rust by example project");
+            Ok(())
+        }
+    }
 }
@@ -0,0 +1,380 @@
+/*!
+ * @Author: ZZX
+ * @Description: 按名字创建动物
+ * `zt_trait::third` 的 `random_animal` 只能用 if/else 在 `Sheep` 和 `Cow` 之间选。
+ * `AnimalRegistry` 把种类的名字映射到返回 `Box<dyn Animal>` 的构造函数，
+ * 别的模块可以往里面登记自己的动物（`farm::register` 登记了农场里的三种），
+ * 然后就能用 `cow name=Bessie` 这样的一行配置创建动物。
+ * @Date: create in 2026/10/19 5:46 上午
+ */
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use crate::toolkit::farm::{self, Animal};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FactoryError {
+    /// 空行。
+    Empty,
+    /// 不是 `key=value` 的参数，或者同一个参数出现了两次。
+    Syntax(String),
+    UnknownKind {
+        kind: String,
+        known: Vec<&'static str>,
+    },
+    DuplicateKind(&'static str),
+    MissingAttribute {
+        kind: String,
+        attribute: &'static str,
+    },
+    InvalidAttribute {
+        kind: String,
+        attribute: &'static str,
+        value: String,
+    },
+    /// 构造函数没有用到的参数。
+    UnknownAttribute {
+        kind: String,
+        attribute: String,
+    },
+}
+
+impl Display for FactoryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            FactoryError::Empty => write!(f, "expected an animal kind"),
+            FactoryError::Syntax(message) => write!(f, "{}", message),
+            FactoryError::UnknownKind { kind, known } => write!(
+                f,
+                "unknown animal kind `{}`, expected one of: {}",
+                kind,
+                known.join(", ")
+            ),
+            FactoryError::DuplicateKind(kind) => {
+                write!(f, "animal kind `{}` is already registered", kind)
+            }
+            FactoryError::MissingAttribute { kind, attribute } => {
+                write!(f, "{}: missing `{}`", kind, attribute)
+            }
+            FactoryError::InvalidAttribute {
+                kind,
+                attribute,
+                value,
+            } => write!(f, "{}: invalid value `{}` for `{}`", kind, value, attribute),
+            FactoryError::UnknownAttribute { kind, attribute } => {
+                write!(f, "{}: unknown attribute `{}`", kind, attribute)
+            }
+        }
+    }
+}
+
+/// 一行配置里的 `key=value` 参数。构造函数取走自己认识的，剩下的会被当成错误。
+pub struct Attrs {
+    kind: String,
+    values: BTreeMap<String, String>,
+}
+
+impl Attrs {
+    pub fn kind(&self) -> &str {
+        &self.kind
+    }
+
+    pub fn take(&mut self, key: &'static str) -> Option<String> {
+        self.values.remove(key)
+    }
+
+    pub fn require(&mut self, key: &'static str) -> Result<String, FactoryError> {
+        self.take(key)
+            .ok_or_else(|| FactoryError::MissingAttribute {
+                kind: self.kind.clone(),
+                attribute: key,
+            })
+    }
+
+    /// 取出并解析一个可选的参数。
+    pub fn take_parsed<T: FromStr>(
+        &mut self,
+        key: &'static str,
+    ) -> Result<Option<T>, FactoryError> {
+        match self.take(key) {
+            None => Ok(None),
+            Some(value) => value
+                .parse()
+                .map(Some)
+                .map_err(|_| FactoryError::InvalidAttribute {
+                    kind: self.kind.clone(),
+                    attribute: key,
+                    value,
+                }),
+        }
+    }
+}
+
+pub type Constructor = fn(&mut Attrs) -> Result<Box<dyn Animal>, FactoryError>;
+
+/// 往注册表里登记动物的函数，由提供动物的模块实现。
+pub type Registrar = fn(&mut AnimalRegistry) -> Result<(), FactoryError>;
+
+/// `with_builtin` 会调用的登记函数。
+const BUILTIN: &[Registrar] = &[farm::register];
+
+struct Entry {
+    description: &'static str,
+    build: Constructor,
+}
+
+#[derive(Default)]
+pub struct AnimalRegistry {
+    kinds: BTreeMap<&'static str, Entry>,
+}
+
+impl AnimalRegistry {
+    /// 空的注册表。
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 登记了所有内置动物的注册表。
+    pub fn with_builtin() -> Self {
+        let mut registry = Self::new();
+        for register in BUILTIN {
+            register(&mut registry).expect("built-in animal kinds are unique");
+        }
+        registry
+    }
+
+    pub fn register(
+        &mut self,
+        kind: &'static str,
+        description: &'static str,
+        build: Constructor,
+    ) -> Result<(), FactoryError> {
+        if self.kinds.contains_key(kind) {
+            return Err(FactoryError::DuplicateKind(kind));
+        }
+        self.kinds.insert(kind, Entry { description, build });
+        Ok(())
+    }
+
+    /// 按名字排序的种类和说明。
+    pub fn kinds(&self) -> impl Iterator<Item = (&'static str, &'static str)> + '_ {
+        self.kinds
+            .iter()
+            .map(|(kind, entry)| (*kind, entry.description))
+    }
+
+    pub fn contains(&self, kind: &str) -> bool {
+        self.kinds.contains_key(kind)
+    }
+
+    /// 按一行配置创建动物：种类后面跟着若干 `key=value`，值里不能有空白。
+    pub fn create(&self, line: &str) -> Result<Box<dyn Animal>, FactoryError> {
+        let mut words = line.split_whitespace();
+        let kind = words.next().ok_or(FactoryError::Empty)?;
+        let entry = self
+            .kinds
+            .get(kind)
+            .ok_or_else(|| FactoryError::UnknownKind {
+                kind: kind.to_owned(),
+                known: self.kinds.keys().copied().collect(),
+            })?;
+
+        let mut attrs = Attrs {
+            kind: kind.to_owned(),
+            values: BTreeMap::new(),
+        };
+        for word in words {
+            let (key, value) = word.split_once('=').ok_or_else(|| {
+                FactoryError::Syntax(format!("{}: expected `key=value`, found `{}`", kind, word))
+            })?;
+            if attrs
+                .values
+                .insert(key.to_owned(), value.to_owned())
+                .is_some()
+            {
+                return Err(FactoryError::Syntax(format!(
+                    "{}: `{}` is given twice",
+                    kind, key
+                )));
+            }
+        }
+
+        let animal = (entry.build)(&mut attrs)?;
+        match attrs.values.into_keys().next() {
+            Some(attribute) => Err(FactoryError::UnknownAttribute {
+                kind: kind.to_owned(),
+                attribute,
+            }),
+            None => Ok(animal),
+        }
+    }
+
+    /// 每行一只动物，跳过空行和 `#` 开头的注释；出错时带上行号。
+    pub fn create_all(&self, text: &str) -> Result<Vec<Box<dyn Animal>>, (usize, FactoryError)> {
+        text.lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .map(|(number, line)| self.create(line).map_err(|e| (number, e)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+struct Goat {
+    name: String,
+    hunger: farm::Hunger,
+}
+
+#[cfg(test)]
+impl Animal for Goat {
+    fn new(name: &str) -> Goat {
+        Goat {
+            name: name.to_owned(),
+            hunger: farm::Hunger::default(),
+        }
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn kind(&self) -> &'static str {
+        "goat"
+    }
+
+    fn noise(&self) -> &'static str {
+        "meeeeh!"
+    }
+
+    fn hunger(&self) -> &farm::Hunger {
+        &self.hunger
+    }
+
+    fn hunger_mut(&mut self) -> &mut farm::Hunger {
+        &mut self.hunger
+    }
+
+    fn appetite(&self) -> u32 {
+        1
+    }
+}
+
+#[test]
+fn builtin_kinds() {
+    let registry = AnimalRegistry::with_builtin();
+    let kinds: Vec<&str> = registry.kinds().map(|(kind, _)| kind).collect();
+    assert_eq!(kinds, ["chicken", "cow", "sheep"]);
+
+    let bessie = registry.create("cow name=Bessie").unwrap();
+    assert_eq!(bessie.kind(), "cow");
+    assert_eq!(bessie.talk(), "Bessie says moooooo!");
+
+    let dolly = registry.create("  sheep   name=Dolly naked=true ").unwrap();
+    assert_eq!(dolly.talk(), "Dolly pauses briefly... baaaaah?");
+    let shaun = registry.create("sheep name=Shaun naked=false").unwrap();
+    assert_eq!(shaun.noise(), "baaaaah!");
+}
+
+#[test]
+fn errors() {
+    let registry = AnimalRegistry::with_builtin();
+    let error = |line: &str| registry.create(line).err().expect("should fail");
+
+    assert_eq!(error("   "), FactoryError::Empty);
+    assert_eq!(
+        error("horse name=Ed").to_string(),
+        "unknown animal kind `horse`, expected one of: chicken, cow, sheep"
+    );
+    assert_eq!(
+        error("cow"),
+        FactoryError::MissingAttribute {
+            kind: "cow".to_owned(),
+            attribute: "name"
+        }
+    );
+    assert_eq!(
+        error("sheep name=Dolly naked=maybe").to_string(),
+        "sheep: invalid value `maybe` for `naked`"
+    );
+    assert_eq!(
+        error("chicken name=Henny naked=true"),
+        FactoryError::UnknownAttribute {
+            kind: "chicken".to_owned(),
+            attribute: "naked".to_owned()
+        }
+    );
+    assert_eq!(
+        error("cow Bessie").to_string(),
+        "cow: expected `key=value`, found `Bessie`"
+    );
+    assert_eq!(
+        error("cow name=A name=B").to_string(),
+        "cow: `name` is given twice"
+    );
+}
+
+#[test]
+fn registration_from_other_modules() {
+    fn register_goat(registry: &mut AnimalRegistry) -> Result<(), FactoryError> {
+        registry.register("goat", "eats anything", |attrs| {
+            Ok(Box::new(Goat::new(&attrs.require("name")?)))
+        })
+    }
+
+    let mut registry = AnimalRegistry::with_builtin();
+    assert!(!registry.contains("goat"));
+    register_goat(&mut registry).unwrap();
+    assert_eq!(
+        register_goat(&mut registry),
+        Err(FactoryError::DuplicateKind("goat"))
+    );
+    assert!(registry
+        .kinds()
+        .any(|kind| kind == ("goat", "eats anything")));
+    assert_eq!(
+        registry.create("goat name=Billy").unwrap().noise(),
+        "meeeeh!"
+    );
+
+    // 空的注册表里什么都没有。
+    let empty = AnimalRegistry::new();
+    assert_eq!(
+        empty.create("cow name=Bessie").err(),
+        Some(FactoryError::UnknownKind {
+            kind: "cow".to_owned(),
+            known: Vec::new()
+        })
+    );
+}
+
+#[test]
+fn populate_a_farm() {
+    let registry = AnimalRegistry::with_builtin();
+    let config = "\
+        # 动物们
+        sheep name=Dolly
+        cow name=Bessie
+
+        chicken name=Henny
+    ";
+    let mut farm = farm::Farm::new();
+    for animal in registry.create_all(config).unwrap() {
+        farm.add_boxed(animal);
+    }
+    assert_eq!(
+        farm.chorus(),
+        [
+            "Dolly pauses briefly... baaaaah!",
+            "Bessie says moooooo!",
+            "Henny says cluck!"
+        ]
+    );
+
+    let broken = "sheep name=Dolly\n\ncow\n";
+    let (line, error) = registry.create_all(broken).err().unwrap();
+    assert_eq!(line, 3);
+    assert_eq!(error.to_string(), "cow: missing `name`");
+}
@@ -0,0 +1,408 @@
+/*!
+ * @Author: ZZX
+ * @Description: 微基准测试
+ * `zh_hof::one` 说命令式的循环和迭代器链算出同样的和，`v_for_and_iter` 演示了
+ * `iter`、`into_iter` 和 `iter_mut`，但都没有测过快慢。这里是一个不依赖第三方库的
+ * 计时工具：先预热，再自动确定每个样本的迭代次数，采集多个样本，
+ * 用四分位距剔除离群值，报告中位数和百分位数。
+ * 各章节用 `benches()` 登记成组的变体，同一组里的变体必须算出相同的校验值。
+ * 通过 `cargo run --release -- bench [--quick] [过滤词...]` 比较。
+ * @Date: create in 2026/10/19 5:46 上午
+ */
+
+use std::fmt::{self, Display, Formatter};
+use std::hint::black_box;
+use std::time::Duration;
+
+use crate::toolkit::retry::{Clock, SystemClock};
+use crate::toolkit::slice_stats::SliceStats;
+
+/// 被测函数，返回一个校验值，同组的变体应当相同。
+pub type BenchFn = fn() -> u64;
+
+pub struct Variant {
+    pub name: &'static str,
+    pub run: BenchFn,
+}
+
+/// 同一件事的几种写法。
+pub struct Group {
+    pub chapter: &'static str,
+    pub name: &'static str,
+    pub variants: Vec<Variant>,
+}
+
+impl Group {
+    /// `chapter/name` 包含 `filter` 时匹配。
+    pub fn matches(&self, filter: &str) -> bool {
+        format!("{}/{}", self.chapter, self.name).contains(filter)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Options {
+    /// 正式计时前调用的次数。
+    pub warmup_iters: u32,
+    pub samples: usize,
+    /// 每个样本至少要花这么久，迭代次数从 1 开始翻倍直到满足。
+    pub min_sample_time: Duration,
+    /// 落在 `[Q1 - k·IQR, Q3 + k·IQR]` 之外的样本视为离群值。
+    pub outlier_fence: f64,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            warmup_iters: 10,
+            samples: 30,
+            min_sample_time: Duration::from_millis(1),
+            outlier_fence: 1.5,
+        }
+    }
+}
+
+impl Options {
+    /// 样本少、时间短，适合在测试里跑。
+    pub fn quick() -> Self {
+        Options {
+            warmup_iters: 2,
+            samples: 5,
+            min_sample_time: Duration::from_micros(100),
+            ..Self::default()
+        }
+    }
+}
+
+/// 单位都是每次迭代的纳秒数。
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    /// 剔除离群值之后剩下的样本数。
+    pub kept: usize,
+    pub outliers: usize,
+    pub median: f64,
+    pub p10: f64,
+    pub p90: f64,
+    pub mean: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+/// 剔除离群值后计算统计量；`samples` 不能为空。
+pub fn analyze(samples: &[f64], outlier_fence: f64) -> Stats {
+    let q1 = samples.percentile(25.0).expect("at least one sample");
+    let q3 = samples.percentile(75.0).expect("at least one sample");
+    let iqr = q3 - q1;
+    let (lo, hi) = (q1 - outlier_fence * iqr, q3 + outlier_fence * iqr);
+    let kept: Vec<f64> = samples
+        .iter()
+        .copied()
+        .filter(|x| (lo..=hi).contains(x))
+        .collect();
+
+    Stats {
+        kept: kept.len(),
+        outliers: samples.len() - kept.len(),
+        median: kept.median().unwrap_or(q1),
+        p10: kept.percentile(10.0).unwrap_or(q1),
+        p90: kept.percentile(90.0).unwrap_or(q3),
+        mean: kept.mean().unwrap_or(q1),
+        min: kept.min_value().unwrap_or(q1),
+        max: kept.max_value().unwrap_or(q3),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Measurement {
+    pub iters_per_sample: u64,
+    pub stats: Stats,
+    /// 最后一次调用的返回值。
+    pub checksum: u64,
+}
+
+fn time<C: Clock, F: FnMut() -> u64>(clock: &C, iters: u64, f: &mut F) -> (Duration, u64) {
+    let start = clock.now();
+    let mut checksum = 0;
+    for _ in 0..iters {
+        checksum = black_box(f());
+    }
+    (clock.now() - start, checksum)
+}
+
+/// 用 `clock` 计时，测出 `f` 每次调用的耗时。
+pub fn measure<C: Clock, F: FnMut() -> u64>(clock: &C, options: &Options, mut f: F) -> Measurement {
+    for _ in 0..options.warmup_iters {
+        black_box(f());
+    }
+
+    let mut iters = 1u64;
+    while iters < 1 << 30 && time(clock, iters, &mut f).0 < options.min_sample_time {
+        iters *= 2;
+    }
+
+    let mut checksum = 0;
+    let samples: Vec<f64> = (0..options.samples.max(1))
+        .map(|_| {
+            let (elapsed, sum) = time(clock, iters, &mut f);
+            checksum = sum;
+            elapsed.as_nanos() as f64 / iters as f64
+        })
+        .collect();
+
+    Measurement {
+        iters_per_sample: iters,
+        stats: analyze(&samples, options.outlier_fence),
+        checksum,
+    }
+}
+
+pub struct GroupReport {
+    pub title: String,
+    pub results: Vec<(&'static str, Measurement)>,
+}
+
+impl GroupReport {
+    /// 中位数最小的变体。
+    pub fn fastest(&self) -> Option<&'static str> {
+        self.results
+            .iter()
+            .min_by(|a, b| a.1.stats.median.total_cmp(&b.1.stats.median))
+            .map(|(name, _)| *name)
+    }
+}
+
+impl Display for GroupReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let best = self
+            .results
+            .iter()
+            .map(|(_, m)| m.stats.median)
+            .fold(f64::INFINITY, f64::min);
+
+        writeln!(f, "{}", self.title)?;
+        writeln!(
+            f,
+            "    {:<12} {:>12} {:>12} {:>12} {:>8} {:>8}",
+            "variant", "median ns", "p10 ns", "p90 ns", "outliers", "relative"
+        )?;
+        for (i, (name, m)) in self.results.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            let relative = if best > 0.0 {
+                m.stats.median / best
+            } else {
+                1.0
+            };
+            write!(
+                f,
+                "    {:<12} {:>12.1} {:>12.1} {:>12.1} {:>8} {:>7.2}x",
+                name, m.stats.median, m.stats.p10, m.stats.p90, m.stats.outliers, relative
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// 依次测量一组里的每个变体，并检查它们的校验值是否一致。
+pub fn run_group<C: Clock>(
+    clock: &C,
+    group: &Group,
+    options: &Options,
+) -> Result<GroupReport, String> {
+    let results: Vec<(&'static str, Measurement)> = group
+        .variants
+        .iter()
+        .map(|v| (v.name, measure(clock, options, v.run)))
+        .collect();
+
+    if results
+        .windows(2)
+        .any(|w| w[0].1.checksum != w[1].1.checksum)
+    {
+        let sums: Vec<String> = results
+            .iter()
+            .map(|(name, m)| format!("{} = {}", name, m.checksum))
+            .collect();
+        return Err(format!(
+            "{}/{}: variants disagree: {}",
+            group.chapter,
+            group.name,
+            sums.join(", ")
+        ));
+    }
+
+    Ok(GroupReport {
+        title: format!("{}/{}", group.chapter, group.name),
+        results,
+    })
+}
+
+/// 所有章节登记的基准测试。
+pub fn registry() -> Vec<Group> {
+    use crate::example::{v_for_and_iter, zh_hof};
+
+    let mut groups = zh_hof::benches();
+    groups.extend(v_for_and_iter::benches());
+    groups
+}
+
+/// `bench` 子命令：`bench [--quick] [--samples N] [过滤词...]`。
+pub fn run(args: &[String]) -> Result<(), String> {
+    let mut options = Options::default();
+    let mut filters = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--quick" => options = Options::quick(),
+            "--samples" => {
+                options.samples = iter
+                    .next()
+                    .and_then(|n| n.parse().ok())
+                    .filter(|&n| n > 0)
+                    .ok_or("`--samples` needs a positive number")?;
+            }
+            filter => filters.push(filter),
+        }
+    }
+
+    let groups: Vec<Group> = registry()
+        .into_iter()
+        .filter(|g| filters.is_empty() || filters.iter().any(|f| g.matches(f)))
+        .collect();
+    if groups.is_empty() {
+        return Err(format!("no benchmark matches {:?}", filters));
+    }
+
+    if cfg!(debug_assertions) {
+        println!(
+            "note: this is a debug build, use `cargo run --release -- bench` for real numbers\n"
+        );
+    }
+    let clock = SystemClock::default();
+    for group in &groups {
+        println!("{}\n", run_group(&clock, group, &options)?);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+use crate::toolkit::retry::FakeClock;
+
+#[test]
+fn outliers_are_rejected() {
+    let mut samples = vec![10.0, 11.0, 9.0, 10.0, 12.0, 10.0, 9.5, 10.5];
+    samples.push(100.0);
+    samples.push(0.1);
+
+    let stats = analyze(&samples, 1.5);
+    assert_eq!((stats.kept, stats.outliers), (8, 2));
+    assert_eq!(stats.median, 10.0);
+    assert_eq!((stats.min, stats.max), (9.0, 12.0));
+    assert!(stats.p10 <= stats.median && stats.median <= stats.p90);
+
+    // 所有样本相同时 IQR 为 0，一个也不剔除。
+    let flat = analyze(&[5.0; 4], 1.5);
+    assert_eq!((flat.kept, flat.median, flat.p90), (4, 5.0, 5.0));
+}
+
+#[test]
+fn measure_with_fake_clock() {
+    let clock = FakeClock::new();
+    let options = Options {
+        warmup_iters: 3,
+        samples: 7,
+        min_sample_time: Duration::from_micros(10),
+        outlier_fence: 1.5,
+    };
+
+    // 每次调用“花费” 1µs。
+    let mut calls = 0u64;
+    let m = measure(&clock, &options, || {
+        clock.advance(Duration::from_micros(1));
+        calls += 1;
+        42
+    });
+
+    // 1, 2, 4, 8 次都不够 10µs，16 次才够。
+    assert_eq!(m.iters_per_sample, 16);
+    assert_eq!(m.stats.median, 1_000.0);
+    assert_eq!(m.stats.outliers, 0);
+    assert_eq!(m.checksum, 42);
+    assert_eq!(calls, 3 + (1 + 2 + 4 + 8 + 16) + 7 * 16);
+}
+
+#[test]
+fn groups_must_agree() {
+    let clock = FakeClock::new();
+    let group = Group {
+        chapter: "test",
+        name: "disagree",
+        variants: vec![
+            Variant {
+                name: "one",
+                run: || 1,
+            },
+            Variant {
+                name: "two",
+                run: || 2,
+            },
+        ],
+    };
+    let options = Options {
+        min_sample_time: Duration::ZERO,
+        ..Options::quick()
+    };
+    assert_eq!(
+        run_group(&clock, &group, &options).err(),
+        Some("test/disagree: variants disagree: one = 1, two = 2".to_string())
+    );
+}
+
+#[test]
+fn chapter_variants_agree() {
+    let groups = registry();
+    assert!(groups.iter().any(|g| g.matches("zh_hof")));
+    assert!(groups.iter().any(|g| g.matches("v_for_and_iter")));
+
+    for group in &groups {
+        assert!(group.variants.len() >= 2);
+        let first = (group.variants[0].run)();
+        for variant in &group.variants {
+            assert_eq!((variant.run)(), first, "{}/{}", group.name, variant.name);
+        }
+    }
+}
+
+#[test]
+fn report_and_runner() {
+    // 计时由假的时钟给出，这里只检查报告的格式，不比较快慢。
+    let clock = FakeClock::new();
+    let group = Group {
+        chapter: "test",
+        name: "sum",
+        variants: vec![
+            Variant {
+                name: "loop",
+                run: || (0..100u64).sum(),
+            },
+            Variant {
+                name: "formula",
+                run: || 99 * 100 / 2,
+            },
+        ],
+    };
+    let options = Options {
+        min_sample_time: Duration::ZERO,
+        ..Options::quick()
+    };
+    let report = run_group(&clock, &group, &options).unwrap();
+    assert_eq!(report.results.len(), 2);
+    assert!(report.fastest().is_some());
+
+    let text = report.to_string();
+    assert!(text.starts_with("test/sum\n    variant"));
+    assert_eq!(text.lines().count(), 4);
+
+    assert!(run(&["nothing-matches-this".to_string()]).is_err());
+    assert!(run(&["--samples".to_string(), "0".to_string()]).is_err());
+}
@@ -0,0 +1,734 @@
+/*!
+ * @Author: ZZX
+ * @Description: 位集合
+ * `i_literals_and_operators` 里的位运算只作用在一个字上。这里提供
+ * 单个字的位操作技巧，定长的 `BitSet<W>`（由 `[u64; W]` 存储，
+ * 容量为 `64 * W` 位）以及可以增长的 `GrowableBitSet`。
+ * 两者都支持置位、清零、翻转、测试、计数、前导/尾随零、rank/select、
+ * 区间遍历和集合运算。
+ * @Date: create in 2026/10/19 5:46 上午
+ */
+
+use std::fmt::{self, Debug, Formatter};
+use std::ops::{BitAnd, BitOr, BitXor, Range, Sub};
+
+const BITS: usize = 64;
+
+/// 只保留最低位的 1。
+pub fn lowest_one(x: u64) -> u64 {
+    x & x.wrapping_neg()
+}
+
+/// 清掉最低位的 1。
+pub fn clear_lowest_one(x: u64) -> u64 {
+    x & x.wrapping_sub(1)
+}
+
+/// 第 `k` 个（从 0 开始）为 1 的位的下标。
+pub fn select_in_word(mut x: u64, k: u32) -> Option<u32> {
+    for _ in 0..k {
+        x = clear_lowest_one(x);
+    }
+    if x == 0 {
+        None
+    } else {
+        Some(x.trailing_zeros())
+    }
+}
+
+/// `range` 内的位全为 1 的掩码，`range` 必须落在 `0..=64` 之内。
+pub fn mask(range: Range<u32>) -> u64 {
+    let high = if range.end >= 64 {
+        u64::MAX
+    } else {
+        (1u64 << range.end) - 1
+    };
+    let low = if range.start >= 64 {
+        u64::MAX
+    } else {
+        (1u64 << range.start) - 1
+    };
+    high & !low
+}
+
+// 下面这些函数只依赖字的切片，定长和可增长的版本共用。
+
+fn word_and_bit(i: usize) -> (usize, u64) {
+    (i / BITS, 1 << (i % BITS))
+}
+
+fn contains(words: &[u64], i: usize) -> bool {
+    let (w, bit) = word_and_bit(i);
+    words.get(w).is_some_and(|word| word & bit != 0)
+}
+
+fn count(words: &[u64]) -> usize {
+    words.iter().map(|w| w.count_ones() as usize).sum()
+}
+
+fn trailing_zeros(words: &[u64]) -> usize {
+    let mut n = 0;
+    for w in words {
+        if *w != 0 {
+            return n + w.trailing_zeros() as usize;
+        }
+        n += BITS;
+    }
+    n
+}
+
+fn leading_zeros(words: &[u64]) -> usize {
+    let mut n = 0;
+    for w in words.iter().rev() {
+        if *w != 0 {
+            return n + w.leading_zeros() as usize;
+        }
+        n += BITS;
+    }
+    n
+}
+
+fn rank(words: &[u64], i: usize) -> usize {
+    let (w, _) = word_and_bit(i);
+    let full: usize = words.iter().take(w).map(|x| x.count_ones() as usize).sum();
+    let partial = words.get(w).map_or(0, |x| {
+        (x & mask(0..(i % BITS) as u32)).count_ones() as usize
+    });
+    full + partial
+}
+
+fn select(words: &[u64], mut k: usize) -> Option<usize> {
+    for (w, word) in words.iter().enumerate() {
+        let ones = word.count_ones() as usize;
+        if k < ones {
+            return select_in_word(*word, k as u32).map(|b| w * BITS + b as usize);
+        }
+        k -= ones;
+    }
+    None
+}
+
+/// 按升序遍历为 1 的位。
+pub struct Ones<'a> {
+    words: &'a [u64],
+    index: usize,
+    current: u64,
+    end: usize,
+}
+
+impl<'a> Ones<'a> {
+    fn new(words: &'a [u64], range: Range<usize>) -> Ones<'a> {
+        let end = range.end.min(words.len() * BITS);
+        let start = range.start.min(end);
+        let index = start / BITS;
+        let current = words
+            .get(index)
+            .map_or(0, |w| w & !mask(0..(start % BITS) as u32));
+        Ones {
+            words,
+            index,
+            current,
+            end,
+        }
+    }
+}
+
+impl Iterator for Ones<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        loop {
+            if self.current != 0 {
+                let i = self.index * BITS + self.current.trailing_zeros() as usize;
+                if i >= self.end {
+                    return None;
+                }
+                self.current = clear_lowest_one(self.current);
+                return Some(i);
+            }
+            self.index += 1;
+            if self.index * BITS >= self.end {
+                return None;
+            }
+            self.current = self.words[self.index];
+        }
+    }
+}
+
+/// 容量为 `64 * W` 位的定长位集合。
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BitSet<const W: usize> {
+    words: [u64; W],
+}
+
+impl<const W: usize> Default for BitSet<W> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const W: usize> BitSet<W> {
+    pub const CAPACITY: usize = W * BITS;
+
+    pub fn new() -> Self {
+        BitSet { words: [0; W] }
+    }
+
+    pub fn from_words(words: [u64; W]) -> Self {
+        BitSet { words }
+    }
+
+    pub fn words(&self) -> &[u64; W] {
+        &self.words
+    }
+
+    fn check(i: usize) -> (usize, u64) {
+        assert!(
+            i < Self::CAPACITY,
+            "bit {} out of range for BitSet of {} bits",
+            i,
+            Self::CAPACITY
+        );
+        word_and_bit(i)
+    }
+
+    /// 置位，返回这一位原来是否为 0。
+    ///
+    /// # Panics
+    ///
+    /// `i >= CAPACITY` 时 panic。
+    pub fn insert(&mut self, i: usize) -> bool {
+        let (w, bit) = Self::check(i);
+        let was_clear = self.words[w] & bit == 0;
+        self.words[w] |= bit;
+        was_clear
+    }
+
+    /// 清零，返回这一位原来是否为 1。超出容量的位本来就是 0。
+    pub fn remove(&mut self, i: usize) -> bool {
+        if i >= Self::CAPACITY {
+            return false;
+        }
+        let (w, bit) = word_and_bit(i);
+        let was_set = self.words[w] & bit != 0;
+        self.words[w] &= !bit;
+        was_set
+    }
+
+    /// 翻转，返回翻转后的值。
+    ///
+    /// # Panics
+    ///
+    /// `i >= CAPACITY` 时 panic。
+    pub fn toggle(&mut self, i: usize) -> bool {
+        let (w, bit) = Self::check(i);
+        self.words[w] ^= bit;
+        self.words[w] & bit != 0
+    }
+
+    pub fn contains(&self, i: usize) -> bool {
+        contains(&self.words, i)
+    }
+
+    pub fn clear(&mut self) {
+        self.words = [0; W];
+    }
+
+    pub fn len(&self) -> usize {
+        count(&self.words)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|w| *w == 0)
+    }
+
+    /// 从第 0 位开始连续的 0 的个数；空集合返回 `CAPACITY`。
+    pub fn trailing_zeros(&self) -> usize {
+        trailing_zeros(&self.words)
+    }
+
+    /// 从最高位开始连续的 0 的个数；空集合返回 `CAPACITY`。
+    pub fn leading_zeros(&self) -> usize {
+        leading_zeros(&self.words)
+    }
+
+    pub fn first(&self) -> Option<usize> {
+        self.iter().next()
+    }
+
+    pub fn last(&self) -> Option<usize> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(Self::CAPACITY - 1 - self.leading_zeros())
+        }
+    }
+
+    /// 小于 `i` 的元素个数。
+    pub fn rank(&self, i: usize) -> usize {
+        rank(&self.words, i)
+    }
+
+    /// 第 `k` 小（从 0 开始）的元素。
+    pub fn select(&self, k: usize) -> Option<usize> {
+        select(&self.words, k)
+    }
+
+    pub fn iter(&self) -> Ones<'_> {
+        Ones::new(&self.words, 0..Self::CAPACITY)
+    }
+
+    /// 只遍历落在 `range` 内的元素。
+    pub fn iter_range(&self, range: Range<usize>) -> Ones<'_> {
+        Ones::new(&self.words, range)
+    }
+
+    pub fn union_with(&mut self, other: &Self) {
+        self.words
+            .iter_mut()
+            .zip(&other.words)
+            .for_each(|(a, b)| *a |= b);
+    }
+
+    pub fn intersect_with(&mut self, other: &Self) {
+        self.words
+            .iter_mut()
+            .zip(&other.words)
+            .for_each(|(a, b)| *a &= b);
+    }
+
+    pub fn difference_with(&mut self, other: &Self) {
+        self.words
+            .iter_mut()
+            .zip(&other.words)
+            .for_each(|(a, b)| *a &= !b);
+    }
+
+    pub fn symmetric_difference_with(&mut self, other: &Self) {
+        self.words
+            .iter_mut()
+            .zip(&other.words)
+            .for_each(|(a, b)| *a ^= b);
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.words
+            .iter()
+            .zip(&other.words)
+            .all(|(a, b)| a & !b == 0)
+    }
+
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        self.words.iter().zip(&other.words).all(|(a, b)| a & b == 0)
+    }
+}
+
+macro_rules! impl_set_op {
+    ($op:ident, $method:ident, $with:ident) => {
+        impl<const W: usize> $op for BitSet<W> {
+            type Output = Self;
+
+            fn $method(mut self, rhs: Self) -> Self {
+                self.$with(&rhs);
+                self
+            }
+        }
+    };
+}
+
+impl_set_op!(BitOr, bitor, union_with);
+impl_set_op!(BitAnd, bitand, intersect_with);
+impl_set_op!(BitXor, bitxor, symmetric_difference_with);
+impl_set_op!(Sub, sub, difference_with);
+
+impl<const W: usize> Debug for BitSet<W> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<const W: usize> std::iter::FromIterator<usize> for BitSet<W> {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for i in iter {
+            set.insert(i);
+        }
+        set
+    }
+}
+
+/// 按需增长的位集合，尾部多余的零字会在比较时被忽略。
+#[derive(Clone, Default)]
+pub struct GrowableBitSet {
+    words: Vec<u64>,
+}
+
+impl GrowableBitSet {
+    pub fn new() -> Self {
+        GrowableBitSet { words: Vec::new() }
+    }
+
+    pub fn with_capacity(bits: usize) -> Self {
+        GrowableBitSet {
+            words: vec![0; bits.div_ceil(BITS)],
+        }
+    }
+
+    /// 当前分配的位数。
+    pub fn capacity(&self) -> usize {
+        self.words.len() * BITS
+    }
+
+    pub fn insert(&mut self, i: usize) -> bool {
+        let (w, bit) = word_and_bit(i);
+        if w >= self.words.len() {
+            self.words.resize(w + 1, 0);
+        }
+        let was_clear = self.words[w] & bit == 0;
+        self.words[w] |= bit;
+        was_clear
+    }
+
+    pub fn remove(&mut self, i: usize) -> bool {
+        let (w, bit) = word_and_bit(i);
+        match self.words.get_mut(w) {
+            Some(word) => {
+                let was_set = *word & bit != 0;
+                *word &= !bit;
+                was_set
+            }
+            None => false,
+        }
+    }
+
+    pub fn toggle(&mut self, i: usize) -> bool {
+        if self.contains(i) {
+            self.remove(i);
+            false
+        } else {
+            self.insert(i);
+            true
+        }
+    }
+
+    pub fn contains(&self, i: usize) -> bool {
+        contains(&self.words, i)
+    }
+
+    pub fn clear(&mut self) {
+        self.words.clear();
+    }
+
+    /// 去掉尾部的零字，释放多余的容量。
+    pub fn shrink_to_fit(&mut self) {
+        while self.words.last() == Some(&0) {
+            self.words.pop();
+        }
+        self.words.shrink_to_fit();
+    }
+
+    pub fn len(&self) -> usize {
+        count(&self.words)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|w| *w == 0)
+    }
+
+    /// 从第 0 位开始连续的 0 的个数；空集合返回当前容量。
+    pub fn trailing_zeros(&self) -> usize {
+        trailing_zeros(&self.words)
+    }
+
+    pub fn first(&self) -> Option<usize> {
+        self.iter().next()
+    }
+
+    pub fn last(&self) -> Option<usize> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.capacity() - 1 - leading_zeros(&self.words))
+        }
+    }
+
+    pub fn rank(&self, i: usize) -> usize {
+        rank(&self.words, i)
+    }
+
+    pub fn select(&self, k: usize) -> Option<usize> {
+        select(&self.words, k)
+    }
+
+    pub fn iter(&self) -> Ones<'_> {
+        Ones::new(&self.words, 0..self.capacity())
+    }
+
+    pub fn iter_range(&self, range: Range<usize>) -> Ones<'_> {
+        Ones::new(&self.words, range)
+    }
+
+    pub fn union_with(&mut self, other: &Self) {
+        if other.words.len() > self.words.len() {
+            self.words.resize(other.words.len(), 0);
+        }
+        self.words
+            .iter_mut()
+            .zip(&other.words)
+            .for_each(|(a, b)| *a |= b);
+    }
+
+    pub fn intersect_with(&mut self, other: &Self) {
+        self.words.truncate(other.words.len());
+        self.words
+            .iter_mut()
+            .zip(&other.words)
+            .for_each(|(a, b)| *a &= b);
+    }
+
+    pub fn difference_with(&mut self, other: &Self) {
+        self.words
+            .iter_mut()
+            .zip(&other.words)
+            .for_each(|(a, b)| *a &= !b);
+    }
+
+    pub fn symmetric_difference_with(&mut self, other: &Self) {
+        if other.words.len() > self.words.len() {
+            self.words.resize(other.words.len(), 0);
+        }
+        self.words
+            .iter_mut()
+            .zip(&other.words)
+            .for_each(|(a, b)| *a ^= b);
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.words
+            .iter()
+            .enumerate()
+            .all(|(i, a)| a & !other.words.get(i).copied().unwrap_or(0) == 0)
+    }
+}
+
+impl PartialEq for GrowableBitSet {
+    fn eq(&self, other: &Self) -> bool {
+        let n = self.words.len().max(other.words.len());
+        (0..n).all(|i| self.words.get(i).unwrap_or(&0) == other.words.get(i).unwrap_or(&0))
+    }
+}
+
+impl Eq for GrowableBitSet {}
+
+impl Debug for GrowableBitSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl std::iter::FromIterator<usize> for GrowableBitSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for i in iter {
+            set.insert(i);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod model {
+    use std::collections::HashSet;
+
+    /// 测试用的线性同余发生器，保证每次运行的操作序列相同。
+    pub struct Lcg(pub u64);
+
+    impl Lcg {
+        pub fn below(&mut self, n: u64) -> usize {
+            self.0 = self
+                .0
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            ((self.0 >> 33) % n) as usize
+        }
+    }
+
+    pub fn sorted(set: &HashSet<usize>) -> Vec<usize> {
+        let mut v: Vec<usize> = set.iter().copied().collect();
+        v.sort_unstable();
+        v
+    }
+}
+
+#[test]
+fn word_tricks() {
+    assert_eq!(lowest_one(0b1011_0000), 0b1_0000);
+    assert_eq!(clear_lowest_one(0b1011_0000), 0b1010_0000);
+    assert_eq!(select_in_word(0b1011_0000, 0), Some(4));
+    assert_eq!(select_in_word(0b1011_0000, 2), Some(7));
+    assert_eq!(select_in_word(0b1011_0000, 3), None);
+    assert_eq!(mask(2..5), 0b11100);
+    assert_eq!(mask(0..64), u64::MAX);
+    assert_eq!(mask(64..64), 0);
+}
+
+#[test]
+fn fixed_bitset_against_hash_set() {
+    use std::collections::HashSet;
+
+    let mut set: BitSet<3> = BitSet::new();
+    let mut expected = HashSet::new();
+    let mut rng = model::Lcg(42);
+
+    for _ in 0..5_000 {
+        let i = rng.below(BitSet::<3>::CAPACITY as u64);
+        match rng.below(3) {
+            0 => assert_eq!(set.insert(i), expected.insert(i)),
+            1 => assert_eq!(set.remove(i), expected.remove(&i)),
+            _ => {
+                let now_set = set.toggle(i);
+                if !expected.remove(&i) {
+                    expected.insert(i);
+                }
+                assert_eq!(now_set, expected.contains(&i));
+            }
+        }
+        assert_eq!(set.len(), expected.len());
+    }
+
+    let sorted = model::sorted(&expected);
+    assert_eq!(set.iter().collect::<Vec<_>>(), sorted);
+    assert_eq!(set.first(), sorted.first().copied());
+    assert_eq!(set.last(), sorted.last().copied());
+    assert_eq!(set.trailing_zeros(), sorted[0]);
+    assert_eq!(
+        set.leading_zeros(),
+        BitSet::<3>::CAPACITY - 1 - sorted[sorted.len() - 1]
+    );
+
+    for i in 0..BitSet::<3>::CAPACITY {
+        assert_eq!(set.contains(i), expected.contains(&i));
+        assert_eq!(set.rank(i), sorted.iter().filter(|&&x| x < i).count());
+    }
+    for (k, x) in sorted.iter().enumerate() {
+        assert_eq!(set.select(k), Some(*x));
+    }
+    assert_eq!(set.select(sorted.len()), None);
+
+    let range: Vec<usize> = set.iter_range(50..150).collect();
+    let expected_range: Vec<usize> = sorted
+        .iter()
+        .copied()
+        .filter(|x| (50..150).contains(x))
+        .collect();
+    assert_eq!(range, expected_range);
+}
+
+#[test]
+fn fixed_set_operations() {
+    use std::collections::HashSet;
+
+    let mut rng = model::Lcg(7);
+    let a_model: HashSet<usize> = (0..60).map(|_| rng.below(128)).collect();
+    let b_model: HashSet<usize> = (0..60).map(|_| rng.below(128)).collect();
+    let a: BitSet<2> = a_model.iter().copied().collect();
+    let b: BitSet<2> = b_model.iter().copied().collect();
+
+    let check = |set: BitSet<2>, expected: HashSet<usize>| {
+        assert_eq!(set.iter().collect::<Vec<_>>(), model::sorted(&expected));
+    };
+    check(a | b, a_model.union(&b_model).copied().collect());
+    check(a & b, a_model.intersection(&b_model).copied().collect());
+    check(a - b, a_model.difference(&b_model).copied().collect());
+    check(
+        a ^ b,
+        a_model.symmetric_difference(&b_model).copied().collect(),
+    );
+
+    assert!((a & b).is_subset(&a));
+    assert!((a - b).is_disjoint(&b));
+    assert_eq!(a.is_subset(&b), a_model.is_subset(&b_model));
+}
+
+#[test]
+fn fixed_edge_cases() {
+    let mut set: BitSet<1> = BitSet::new();
+    assert!(set.is_empty());
+    assert_eq!((set.first(), set.last()), (None, None));
+    assert_eq!((set.trailing_zeros(), set.leading_zeros()), (64, 64));
+    assert!(!set.contains(1_000));
+    assert!(!set.remove(1_000));
+
+    set.insert(63);
+    assert_eq!(set.last(), Some(63));
+    assert_eq!(set.leading_zeros(), 0);
+    assert_eq!(format!("{:?}", set), "{63}");
+
+    let result = std::panic::catch_unwind(|| BitSet::<1>::new().insert(64));
+    assert!(result.is_err());
+}
+
+#[test]
+fn growable_bitset_against_hash_set() {
+    use std::collections::HashSet;
+
+    let mut set = GrowableBitSet::new();
+    let mut expected = HashSet::new();
+    let mut rng = model::Lcg(2024);
+
+    for _ in 0..5_000 {
+        let i = rng.below(1_000);
+        match rng.below(3) {
+            0 => assert_eq!(set.insert(i), expected.insert(i)),
+            1 => assert_eq!(set.remove(i), expected.remove(&i)),
+            _ => {
+                let now_set = set.toggle(i);
+                if !expected.remove(&i) {
+                    expected.insert(i);
+                }
+                assert_eq!(now_set, expected.contains(&i));
+            }
+        }
+    }
+
+    let sorted = model::sorted(&expected);
+    assert_eq!(set.len(), sorted.len());
+    assert_eq!(set.iter().collect::<Vec<_>>(), sorted);
+    assert_eq!(set.last(), sorted.last().copied());
+    for (k, x) in sorted.iter().enumerate() {
+        assert_eq!(set.rank(*x), k);
+        assert_eq!(set.select(k), Some(*x));
+    }
+    assert!(set.capacity() >= 1_000);
+}
+
+#[test]
+fn growable_set_operations() {
+    let small: GrowableBitSet = vec![1, 3, 5].into_iter().collect();
+    let large: GrowableBitSet = vec![3, 500].into_iter().collect();
+
+    let mut u = small.clone();
+    u.union_with(&large);
+    assert_eq!(u.iter().collect::<Vec<_>>(), vec![1, 3, 5, 500]);
+
+    let mut i = large.clone();
+    i.intersect_with(&small);
+    assert_eq!(i.iter().collect::<Vec<_>>(), vec![3]);
+
+    let mut d = small.clone();
+    d.difference_with(&large);
+    assert_eq!(d.iter().collect::<Vec<_>>(), vec![1, 5]);
+
+    let mut x = small.clone();
+    x.symmetric_difference_with(&large);
+    assert_eq!(x.iter().collect::<Vec<_>>(), vec![1, 5, 500]);
+
+    // 容量不同但内容相同的集合相等。
+    let mut trimmed = u.clone();
+    trimmed.remove(500);
+    trimmed.shrink_to_fit();
+    assert_eq!(trimmed, small);
+    assert!(small.is_subset(&u));
+    assert!(!u.is_subset(&small));
+    assert_eq!(GrowableBitSet::with_capacity(100).capacity(), 128);
+}
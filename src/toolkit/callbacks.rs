@@ -0,0 +1,284 @@
+/*!
+ * @Author: ZZX
+ * @Description: 回调注册表
+ * 扩展 `zd_closure_capture::one`：那里的 `inc` 通过可变引用修改捕获的计数，
+ * `consume` 按值捕获并丢掉 `movable`，`contains` 用 `move` 拿走了 `haystack`。
+ * `Registry` 把这样的闭包存成 `Box<dyn FnMut(&E)>`，用 `HandlerId` 标识，
+ * 事件发生时按注册顺序调用。`once` 注册的 `FnOnce` 只会被调用一次，之后自动注销；
+ * 处理函数可以带着 `Remover` 在派发过程中注销自己或别的处理函数。
+ * @Date: create in 2026/10/19 5:46 上午
+ */
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct HandlerId(u64);
+
+type RepeatFn<'a, E> = Box<dyn FnMut(&E) + 'a>;
+type OnceFn<'a, E> = Box<dyn FnOnce(&E) + 'a>;
+
+enum Handler<'a, E> {
+    Repeat(RepeatFn<'a, E>),
+    /// 调用之后变成 `None`，随后被注销。
+    Once(Option<OnceFn<'a, E>>),
+}
+
+/// 等待注销的处理函数。派发过程中请求注销的处理函数不会再被调用。
+type Pending = Rc<RefCell<Vec<HandlerId>>>;
+
+pub struct Registry<'a, E> {
+    next_id: u64,
+    handlers: Vec<(HandlerId, Handler<'a, E>)>,
+    pending: Pending,
+}
+
+/// 可以移进处理函数里的注销句柄。
+#[derive(Clone)]
+pub struct Remover {
+    pending: Pending,
+}
+
+impl Remover {
+    /// 注销 `id`。在派发过程中调用时，本轮里排在后面的 `id` 也不会再被调用。
+    pub fn unregister(&self, id: HandlerId) {
+        self.pending.borrow_mut().push(id);
+    }
+}
+
+impl<E> Default for Registry<'_, E> {
+    fn default() -> Self {
+        Registry {
+            next_id: 0,
+            handlers: Vec::new(),
+            pending: Rc::default(),
+        }
+    }
+}
+
+impl<'a, E> Registry<'a, E> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn add(&mut self, handler: Handler<'a, E>) -> HandlerId {
+        let id = HandlerId(self.next_id);
+        self.next_id += 1;
+        self.handlers.push((id, handler));
+        id
+    }
+
+    /// 每次派发都调用 `handler`。
+    pub fn register<F: FnMut(&E) + 'a>(&mut self, handler: F) -> HandlerId {
+        self.add(Handler::Repeat(Box::new(handler)))
+    }
+
+    /// 只在下一次派发时调用 `handler`，之后自动注销。
+    pub fn once<F: FnOnce(&E) + 'a>(&mut self, handler: F) -> HandlerId {
+        self.add(Handler::Once(Some(Box::new(handler))))
+    }
+
+    /// 返回 `id` 是否还在注册表里。
+    pub fn unregister(&mut self, id: HandlerId) -> bool {
+        self.flush();
+        let before = self.handlers.len();
+        self.handlers.retain(|(other, _)| *other != id);
+        self.handlers.len() < before
+    }
+
+    pub fn remover(&self) -> Remover {
+        Remover {
+            pending: Rc::clone(&self.pending),
+        }
+    }
+
+    fn is_pending(&self, id: HandlerId) -> bool {
+        self.pending.borrow().contains(&id)
+    }
+
+    fn flush(&mut self) {
+        let pending: Vec<HandlerId> = self.pending.borrow_mut().drain(..).collect();
+        if !pending.is_empty() {
+            self.handlers.retain(|(id, _)| !pending.contains(id));
+        }
+    }
+
+    pub fn contains(&self, id: HandlerId) -> bool {
+        !self.is_pending(id) && self.handlers.iter().any(|(other, _)| *other == id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.handlers
+            .iter()
+            .filter(|(id, _)| !self.is_pending(*id))
+            .count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 按注册顺序调用所有处理函数，返回调用了几个。
+    pub fn emit(&mut self, event: &E) -> usize {
+        self.flush();
+        let mut called = 0;
+        for i in 0..self.handlers.len() {
+            let id = self.handlers[i].0;
+            // 可能被前面的处理函数注销了。
+            if self.is_pending(id) {
+                continue;
+            }
+            called += 1;
+            match &mut self.handlers[i].1 {
+                Handler::Repeat(handler) => handler(event),
+                Handler::Once(handler) => {
+                    if let Some(handler) = handler.take() {
+                        handler(event);
+                    }
+                    self.pending.borrow_mut().push(id);
+                }
+            }
+        }
+        self.flush();
+        called
+    }
+}
+
+#[cfg(test)]
+use crate::example::m_enum::WebEvent;
+
+#[test]
+fn capture_by_reference_and_mutable_reference() {
+    let color = "green";
+    let mut count = 0;
+    let mut seen = Vec::new();
+    {
+        let mut registry = Registry::new();
+        // `&T`：只读地借用 `color`。
+        registry.register(|_: &WebEvent| assert_eq!(color, "green"));
+        // `&mut T`：借用期间只有处理函数能修改 `count`。
+        registry.register(|_| count += 1);
+        registry.register(|event| {
+            if let WebEvent::KeyPress(c) = event {
+                seen.push(*c);
+            }
+        });
+
+        assert_eq!(registry.emit(&WebEvent::KeyPress('x')), 3);
+        assert_eq!(registry.emit(&WebEvent::PageLoad), 3);
+        assert_eq!(registry.emit(&WebEvent::KeyPress('y')), 3);
+    }
+    // 注册表离开作用域之后借用结束，修改的是外面的变量。
+    assert_eq!(count, 3);
+    assert_eq!(seen, ['x', 'y']);
+}
+
+#[test]
+fn capture_by_move() {
+    let counter = Rc::new(RefCell::new(Vec::new()));
+    let mut count = 0;
+    let haystack = "abc".to_owned();
+
+    let mut registry: Registry<'static, WebEvent> = Registry::new();
+    // `move` 之后闭包里的 `count` 是一份拷贝，外面的 `count` 不受影响。
+    let log = Rc::clone(&counter);
+    registry.register(move |_| {
+        count += 1;
+        log.borrow_mut().push(count);
+    });
+    // `haystack` 被移进闭包，闭包归注册表所有，所以可以是 `'static`。
+    let log = Rc::clone(&counter);
+    registry.register(move |event| {
+        if let WebEvent::KeyPress(c) = event {
+            log.borrow_mut().push(haystack.contains(*c) as i32 * 100);
+        }
+    });
+
+    registry.emit(&WebEvent::KeyPress('a'));
+    registry.emit(&WebEvent::KeyPress('z'));
+    registry.emit(&WebEvent::PageUnload);
+
+    count += 10;
+    assert_eq!(count, 10);
+    assert_eq!(*counter.borrow(), [1, 100, 2, 0, 3]);
+
+    // 丢掉注册表时闭包和它们拥有的 `Rc` 一起被丢掉。
+    assert_eq!(Rc::strong_count(&counter), 3);
+    drop(registry);
+    assert_eq!(Rc::strong_count(&counter), 1);
+}
+
+#[test]
+fn one_shot_handlers() {
+    let movable = Box::new(3);
+    let mut consumed = Vec::new();
+    {
+        let consumed = &mut consumed;
+        let mut registry = Registry::new();
+        // `FnOnce`：按值使用 `movable`，只能调用一次。
+        let id = registry.once(move |event: &WebEvent| {
+            if let WebEvent::Paste(s) = event {
+                consumed.push(format!("{} {}", s, movable));
+            }
+            drop(movable);
+        });
+        assert!(registry.contains(id));
+
+        assert_eq!(registry.emit(&WebEvent::Paste("first".to_owned())), 1);
+        assert!(!registry.contains(id));
+        assert!(registry.is_empty());
+        assert_eq!(registry.emit(&WebEvent::Paste("second".to_owned())), 0);
+        assert!(!registry.unregister(id));
+    }
+    assert_eq!(consumed, ["first 3"]);
+}
+
+#[test]
+fn unregister_during_emit() {
+    let calls = RefCell::new(Vec::new());
+    let mut registry: Registry<'_, WebEvent> = Registry::new();
+    let remover = registry.remover();
+
+    // 第一个处理函数在第二次派发时注销第三个，第三个在本轮里就不会再被调用。
+    // 它要带走 `remover` 和自己的计数，所以用 `move`，`calls` 只移进去一个引用。
+    let third = Rc::new(RefCell::new(None));
+    let target = Rc::clone(&third);
+    let log = &calls;
+    let mut rounds = 0;
+    let first = registry.register(move |_| {
+        rounds += 1;
+        log.borrow_mut().push("first");
+        if rounds == 2 {
+            remover.unregister(target.borrow().expect("registered"));
+        }
+    });
+    let second = registry.register(|_| calls.borrow_mut().push("second"));
+    *third.borrow_mut() = Some(registry.register(|_| calls.borrow_mut().push("third")));
+
+    assert_eq!(registry.emit(&WebEvent::PageLoad), 3);
+    assert_eq!(registry.emit(&WebEvent::PageLoad), 2);
+    assert_eq!(registry.len(), 2);
+    assert_eq!(registry.emit(&WebEvent::PageLoad), 2);
+
+    // 在派发之外通过句柄注销，下一次派发前生效。
+    registry.remover().unregister(second);
+    assert!(!registry.contains(second));
+    assert!(registry.unregister(first));
+    assert_eq!(registry.emit(&WebEvent::PageLoad), 0);
+
+    assert_eq!(
+        *calls.borrow(),
+        ["first", "second", "third", "first", "second", "first", "second"]
+    );
+}
+
+#[test]
+fn ids_are_unique_and_ordered() {
+    let mut registry: Registry<'_, WebEvent> = Registry::new();
+    let a = registry.register(|_| {});
+    let b = registry.once(|_| {});
+    assert!(registry.unregister(a));
+    let c = registry.register(|_| {});
+    assert!(a < b && b < c);
+    assert_eq!(registry.len(), 2);
+}
@@ -0,0 +1,697 @@
+/*!
+ * @Author: ZZX
+ * @Description: 类型转换说明
+ * 扩展 `r_casting::one`：那里只把 `1000 as u8`、`232 as i8`、
+ * `65.4321 as u8 as char` 这些“出人意料”的结果打印出来。
+ * 这里对任意两个原生类型（数值、`char`、`bool`）之间合法的 `as` 转换给出分类
+ * （无损、截断、回绕、饱和、舍入），提供 checked/saturating/wrapping 三种版本，
+ * 并解释结果在二进制位层面是怎么来的。
+ * @Date: create in 2026/10/19 5:46 上午
+ */
+
+use std::convert::TryFrom;
+use std::fmt::{self, Display, Formatter};
+
+/// 一次 `as` 转换属于哪一类。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CastKind {
+    /// 值原样保留，可以无损地转回去。
+    Lossless,
+    /// 整数丢掉高位，或者浮点数丢掉小数部分。
+    Truncating,
+    /// 位模式不变（或经过符号扩展），但按另一种符号解释。
+    Wrapping,
+    /// 超出目标类型的范围，被钳制到边界（浮点转整数、溢出到无穷大）。
+    Saturating,
+    /// 目标浮点类型的精度不够，舍入到最近的可表示值。
+    Rounding,
+}
+
+impl Display for CastKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            CastKind::Lossless => "lossless",
+            CastKind::Truncating => "truncating",
+            CastKind::Wrapping => "wrapping",
+            CastKind::Saturating => "saturating",
+            CastKind::Rounding => "rounding",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// 与具体类型无关的中间表示，所有原生类型都能无损地放进去。
+/// `char` 和 `bool` 按它们的码点和 0/1 放进 `Unsigned`。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Repr {
+    Signed(i128),
+    Unsigned(u128),
+    Float(f64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Class {
+    Signed,
+    Unsigned,
+    Float,
+    Char,
+    Bool,
+}
+
+/// 参与转换的原生类型。
+pub trait Primitive: Copy + Display {
+    const NAME: &'static str;
+    const BITS: u32;
+    const CLASS: Class;
+
+    fn repr(self) -> Repr;
+    /// 内存中的原始位模式，零扩展到 128 位。
+    fn raw_bits(self) -> u128;
+    /// 只有能无损表示时才返回 `Some`。
+    fn checked_from(repr: Repr) -> Option<Self>;
+    fn saturating_from(repr: Repr) -> Self;
+    fn wrapping_from(repr: Repr) -> Self;
+
+    /// 解释里怎么写这个值，数值带上类型后缀，比如 `232u8`。
+    fn literal(self) -> String {
+        format!("{}{}", self, Self::NAME)
+    }
+}
+
+/// 目标整数类型能表示的范围，`[lower, upper)`，用 `f64` 精确表示。
+fn float_bounds(bits: u32, class: Class) -> (f64, f64) {
+    match class {
+        Class::Signed => (-(2f64.powi(bits as i32 - 1)), 2f64.powi(bits as i32 - 1)),
+        _ => (0.0, 2f64.powi(bits as i32)),
+    }
+}
+
+/// 浮点数向零取整后是否落在目标整数类型的范围内。
+fn float_in_range(f: f64, bits: u32, class: Class) -> bool {
+    let (lower, upper) = float_bounds(bits, class);
+    let t = f.trunc();
+    t >= lower && t < upper
+}
+
+/// 一个整数去掉首尾的 0 之后需要多少个有效位。
+fn significant_bits(magnitude: u128) -> u32 {
+    if magnitude == 0 {
+        0
+    } else {
+        128 - magnitude.leading_zeros() - magnitude.trailing_zeros()
+    }
+}
+
+macro_rules! impl_int_primitive {
+    ($($t:ty => $class:ident),* $(,)?) => {$(
+        impl Primitive for $t {
+            const NAME: &'static str = stringify!($t);
+            const BITS: u32 = <$t>::BITS;
+            const CLASS: Class = Class::$class;
+
+            fn repr(self) -> Repr {
+                match Self::CLASS {
+                    Class::Signed => Repr::Signed(self as i128),
+                    _ => Repr::Unsigned(self as u128),
+                }
+            }
+
+            fn raw_bits(self) -> u128 {
+                (self as u128) & (u128::MAX >> (128 - Self::BITS))
+            }
+
+            fn checked_from(repr: Repr) -> Option<Self> {
+                match repr {
+                    Repr::Signed(v) => <$t>::try_from(v).ok(),
+                    Repr::Unsigned(v) => <$t>::try_from(v).ok(),
+                    Repr::Float(f) => {
+                        if f.is_finite()
+                            && f.fract() == 0.0
+                            && float_in_range(f, Self::BITS, Self::CLASS)
+                        {
+                            Some(f as $t)
+                        } else {
+                            None
+                        }
+                    }
+                }
+            }
+
+            fn saturating_from(repr: Repr) -> Self {
+                match repr {
+                    Repr::Signed(v) => {
+                        <$t>::try_from(v).unwrap_or(if v < 0 { <$t>::MIN } else { <$t>::MAX })
+                    }
+                    Repr::Unsigned(v) => <$t>::try_from(v).unwrap_or(<$t>::MAX),
+                    // 浮点数转整数的 `as` 本身就是饱和的，NaN 变成 0。
+                    Repr::Float(f) => f as $t,
+                }
+            }
+
+            fn wrapping_from(repr: Repr) -> Self {
+                match repr {
+                    Repr::Signed(v) => v as $t,
+                    Repr::Unsigned(v) => v as $t,
+                    Repr::Float(f) => {
+                        if !f.is_finite() {
+                            return 0;
+                        }
+                        // `rem_euclid` 对浮点数是精确的，结果落在 `[0, 2^BITS)`。
+                        let m = f.trunc().rem_euclid(2f64.powi(Self::BITS as i32));
+                        m as u128 as $t
+                    }
+                }
+            }
+        }
+    )*};
+}
+
+impl_int_primitive!(
+    i8 => Signed, i16 => Signed, i32 => Signed, i64 => Signed, i128 => Signed, isize => Signed,
+    u8 => Unsigned, u16 => Unsigned, u32 => Unsigned, u64 => Unsigned, u128 => Unsigned,
+    usize => Unsigned,
+);
+
+macro_rules! impl_float_primitive {
+    ($($t:ident),*) => {$(
+        impl Primitive for $t {
+            const NAME: &'static str = stringify!($t);
+            const BITS: u32 = std::mem::size_of::<$t>() as u32 * 8;
+            const CLASS: Class = Class::Float;
+
+            fn repr(self) -> Repr {
+                Repr::Float(self as f64)
+            }
+
+            fn raw_bits(self) -> u128 {
+                self.to_bits() as u128
+            }
+
+            fn checked_from(repr: Repr) -> Option<Self> {
+                match repr {
+                    Repr::Signed(v) => {
+                        if significant_bits(v.unsigned_abs()) <= $t::MANTISSA_DIGITS {
+                            Some(v as $t)
+                        } else {
+                            None
+                        }
+                    }
+                    Repr::Unsigned(v) => {
+                        if significant_bits(v) <= $t::MANTISSA_DIGITS {
+                            Some(v as $t)
+                        } else {
+                            None
+                        }
+                    }
+                    Repr::Float(f) => {
+                        let y = f as $t;
+                        if f.is_nan() || y as f64 == f {
+                            Some(y)
+                        } else {
+                            None
+                        }
+                    }
+                }
+            }
+
+            // 转到浮点数时 `as` 总是舍入到最近值，三个版本没有区别。
+            fn saturating_from(repr: Repr) -> Self {
+                Self::wrapping_from(repr)
+            }
+
+            fn wrapping_from(repr: Repr) -> Self {
+                match repr {
+                    Repr::Signed(v) => v as $t,
+                    Repr::Unsigned(v) => v as $t,
+                    Repr::Float(f) => f as $t,
+                }
+            }
+        }
+    )*};
+}
+
+impl_float_primitive!(f32, f64);
+
+// 只有 `u8` 能 `as char`，所以饱和和回绕版本都按 `x as u8 as char` 处理；
+// 检查版本接受任何合法的码点。
+impl Primitive for char {
+    const NAME: &'static str = "char";
+    const BITS: u32 = 32;
+    const CLASS: Class = Class::Char;
+
+    fn repr(self) -> Repr {
+        Repr::Unsigned(self as u128)
+    }
+
+    fn raw_bits(self) -> u128 {
+        self as u128
+    }
+
+    fn checked_from(repr: Repr) -> Option<Self> {
+        u32::checked_from(repr).and_then(char::from_u32)
+    }
+
+    fn saturating_from(repr: Repr) -> Self {
+        char::from(u8::saturating_from(repr))
+    }
+
+    fn wrapping_from(repr: Repr) -> Self {
+        char::from(u8::wrapping_from(repr))
+    }
+
+    fn literal(self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+// 没有任何类型能 `as bool`：饱和版本把值钳制到 0 和 1 之间，回绕版本只看最低位。
+impl Primitive for bool {
+    const NAME: &'static str = "bool";
+    const BITS: u32 = 8;
+    const CLASS: Class = Class::Bool;
+
+    fn repr(self) -> Repr {
+        Repr::Unsigned(self as u128)
+    }
+
+    fn raw_bits(self) -> u128 {
+        self as u128
+    }
+
+    fn checked_from(repr: Repr) -> Option<Self> {
+        match u8::checked_from(repr) {
+            Some(0) => Some(false),
+            Some(1) => Some(true),
+            _ => None,
+        }
+    }
+
+    fn saturating_from(repr: Repr) -> Self {
+        u8::saturating_from(repr) != 0
+    }
+
+    fn wrapping_from(repr: Repr) -> Self {
+        u8::wrapping_from(repr) & 1 == 1
+    }
+
+    fn literal(self) -> String {
+        self.to_string()
+    }
+}
+
+/// 语言内建的 `x as T`，用来和上面的模型互相验证。
+pub trait CastAs<T> {
+    fn cast_as(self) -> T;
+}
+
+macro_rules! impl_cast_as {
+    ($($src:ty),*; $dst:tt) => {
+        $(impl_cast_as!(@row $src; $dst);)*
+    };
+    (@row $src:ty; [$($dst:ty),*]) => {$(
+        impl CastAs<$dst> for $src {
+            #[allow(clippy::unnecessary_cast)]
+            fn cast_as(self) -> $dst {
+                self as $dst
+            }
+        }
+    )*};
+}
+
+impl_cast_as!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64;
+    [i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64]
+);
+impl_cast_as!(
+    char, bool;
+    [i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize]
+);
+impl_cast_as!(u8; [char]);
+
+/// 一次转换的完整报告。
+#[derive(Debug, Clone, PartialEq)]
+pub struct CastReport<T> {
+    pub value: T,
+    pub kind: CastKind,
+    pub explanation: String,
+}
+
+impl<T: Primitive> Display for CastReport<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.kind, self.explanation)
+    }
+}
+
+pub fn checked_cast<S: Primitive, T: Primitive>(x: S) -> Option<T> {
+    T::checked_from(x.repr())
+}
+
+pub fn saturating_cast<S: Primitive, T: Primitive>(x: S) -> T {
+    T::saturating_from(x.repr())
+}
+
+pub fn wrapping_cast<S: Primitive, T: Primitive>(x: S) -> T {
+    T::wrapping_from(x.repr())
+}
+
+pub fn classify<S: Primitive, T: Primitive>(x: S) -> CastKind {
+    let repr = x.repr();
+    if T::checked_from(repr).is_some() {
+        return CastKind::Lossless;
+    }
+
+    match (S::CLASS, T::CLASS) {
+        (_, Class::Float) => match T::wrapping_from(repr).repr() {
+            Repr::Float(f) if f.is_infinite() => CastKind::Saturating,
+            _ => CastKind::Rounding,
+        },
+        (Class::Float, _) => match repr {
+            Repr::Float(f) if f.is_finite() && float_in_range(f, T::BITS, T::CLASS) => {
+                CastKind::Truncating
+            }
+            _ => CastKind::Saturating,
+        },
+        _ => {
+            if T::BITS < S::BITS {
+                CastKind::Truncating
+            } else {
+                CastKind::Wrapping
+            }
+        }
+    }
+}
+
+fn binary(bits: u128, width: u32) -> String {
+    format!("0b{:0width$b}", bits, width = width as usize)
+}
+
+/// 对 `x as T` 做完整的说明。
+pub fn explain<S, T>(x: S) -> CastReport<T>
+where
+    S: Primitive + CastAs<T>,
+    T: Primitive,
+{
+    let value: T = x.cast_as();
+    let kind = classify::<S, T>(x);
+    let from = x.literal();
+    let to = value.literal();
+
+    let explanation = match (kind, S::CLASS, T::CLASS) {
+        (_, _, Class::Char) => {
+            format!(
+                "{} is the code point U+{:04X}: {}",
+                from,
+                value.raw_bits(),
+                to
+            )
+        }
+        (CastKind::Lossless, Class::Char, _) => {
+            format!("{} is the code point U+{:04X}: {}", from, x.raw_bits(), to)
+        }
+        (_, Class::Bool, _) => format!("{} is stored as {}: {}", from, x.raw_bits(), to),
+        (CastKind::Lossless, ..) => format!("{} fits in {}: {}", from, T::NAME, to),
+        (CastKind::Truncating, Class::Float, _) => {
+            format!(
+                "{} has a fractional part, truncated toward zero: {}",
+                from, to
+            )
+        }
+        (CastKind::Truncating, ..) => format!(
+            "{} = {}, keep the low {} bits {} = {}",
+            from,
+            binary(x.raw_bits(), S::BITS),
+            T::BITS,
+            binary(value.raw_bits(), T::BITS),
+            to
+        ),
+        (CastKind::Wrapping, ..) if T::BITS > S::BITS => format!(
+            "{} = {}, sign-extended to {} bits {} and read as {}: {}",
+            from,
+            binary(x.raw_bits(), S::BITS),
+            T::BITS,
+            binary(value.raw_bits(), T::BITS),
+            T::NAME,
+            to
+        ),
+        (CastKind::Wrapping, ..) => format!(
+            "{} = {}, same bits read as {}: {}",
+            from,
+            binary(x.raw_bits(), S::BITS),
+            T::NAME,
+            to
+        ),
+        (CastKind::Saturating, Class::Float, Class::Float) => {
+            format!(
+                "{} is out of the range of {}, overflowed to {}",
+                from,
+                T::NAME,
+                to
+            )
+        }
+        (CastKind::Saturating, Class::Float, _) => match x.repr() {
+            Repr::Float(f) if f.is_nan() => format!("{} is NaN, which maps to {}", from, to),
+            Repr::Float(f) if f < 0.0 => {
+                format!("{} is below {}::MIN, saturated to {}", from, T::NAME, to)
+            }
+            _ => format!("{} is above {}::MAX, saturated to {}", from, T::NAME, to),
+        },
+        (CastKind::Saturating, ..) => {
+            format!(
+                "{} is out of the range of {}, rounded to {}",
+                from,
+                T::NAME,
+                to
+            )
+        }
+        (CastKind::Rounding, Class::Float, _) => format!(
+            "{} is not representable in {}, rounded to nearest: {:?}",
+            from,
+            T::NAME,
+            value.repr()
+        ),
+        (CastKind::Rounding, ..) => {
+            let magnitude = match x.repr() {
+                Repr::Signed(v) => v.unsigned_abs(),
+                Repr::Unsigned(v) => v,
+                Repr::Float(_) => unreachable!(),
+            };
+            format!(
+                "{} needs {} significant bits but {} has {}, rounded to {}",
+                from,
+                significant_bits(magnitude),
+                T::NAME,
+                if T::BITS == 32 {
+                    f32::MANTISSA_DIGITS
+                } else {
+                    f64::MANTISSA_DIGITS
+                },
+                to
+            )
+        }
+    };
+
+    CastReport {
+        value,
+        kind,
+        explanation,
+    }
+}
+
+#[test]
+fn chapter_examples() {
+    let r = explain::<_, u8>(1000i32);
+    assert_eq!(r.value, 232);
+    assert_eq!(r.kind, CastKind::Truncating);
+    assert_eq!(
+        r.explanation,
+        "1000i32 = 0b00000000000000000000001111101000, keep the low 8 bits 0b11101000 = 232u8"
+    );
+
+    let r = explain::<_, i8>(232u8);
+    assert_eq!((r.value, r.kind), (-24, CastKind::Wrapping));
+    assert_eq!(
+        r.explanation,
+        "232u8 = 0b11101000, same bits read as i8: -24i8"
+    );
+
+    let r = explain::<_, u8>(65.4321f32);
+    assert_eq!((r.value, r.kind), (65, CastKind::Truncating));
+    assert_eq!(
+        r.explanation,
+        "65.4321f32 has a fractional part, truncated toward zero: 65u8"
+    );
+
+    let r = explain::<_, u32>(-1i8);
+    assert_eq!((r.value, r.kind), (u32::MAX, CastKind::Wrapping));
+    assert!(r.explanation.contains("sign-extended to 32 bits"));
+
+    assert_eq!(explain::<_, u16>(1000i32).kind, CastKind::Lossless);
+    assert_eq!(explain::<_, i16>(128i32).kind, CastKind::Lossless);
+    assert_eq!(explain::<_, i8>(128i32).kind, CastKind::Truncating);
+}
+
+#[test]
+fn float_sources() {
+    assert_eq!(explain::<_, u8>(300.5f64).kind, CastKind::Saturating);
+    assert_eq!(explain::<_, u8>(-0.5f64).kind, CastKind::Truncating);
+    assert_eq!(explain::<_, u8>(-1.0f64).kind, CastKind::Saturating);
+    assert_eq!(explain::<_, i32>(f32::NAN).value, 0);
+    assert_eq!(
+        explain::<_, i32>(f32::NAN).explanation,
+        "NaNf32 is NaN, which maps to 0i32"
+    );
+    assert_eq!(explain::<_, f32>(0.5f64).kind, CastKind::Lossless);
+    assert_eq!(explain::<_, f32>(0.1f64).kind, CastKind::Rounding);
+    assert_eq!(explain::<_, f32>(1e300f64).kind, CastKind::Saturating);
+    assert_eq!(explain::<_, f64>(f32::NAN).kind, CastKind::Lossless);
+
+    // 2^31 恰好超出 i32 的范围，`as` 会饱和到 `i32::MAX`。
+    assert_eq!(checked_cast::<_, i32>(2_147_483_648f32), None);
+    assert_eq!(saturating_cast::<_, i32>(2_147_483_648f32), i32::MAX);
+    assert_eq!(wrapping_cast::<_, i32>(2_147_483_648f32), i32::MIN);
+    assert_eq!(wrapping_cast::<_, u8>(-1.9f64), 255);
+    assert_eq!(wrapping_cast::<_, u8>(f64::INFINITY), 0);
+}
+
+#[test]
+fn integer_to_float_precision() {
+    let r = explain::<_, f32>(16_777_217i32);
+    assert_eq!(r.kind, CastKind::Rounding);
+    assert_eq!(
+        r.explanation,
+        "16777217i32 needs 25 significant bits but f32 has 24, rounded to 16777216f32"
+    );
+    assert_eq!(explain::<_, f32>(16_777_216i32).kind, CastKind::Lossless);
+    assert_eq!(explain::<_, f64>(i64::MIN).kind, CastKind::Lossless);
+    assert_eq!(explain::<_, f32>(u128::MAX).kind, CastKind::Saturating);
+    assert_eq!(checked_cast::<_, f64>(u64::MAX), None);
+}
+
+// 对一个整数源值，按它的数学值检查报告与各个变体是否一致。
+#[cfg(test)]
+fn check_int_source<S, T>(x: S, v: i128)
+where
+    S: Primitive + CastAs<T>,
+    T: Primitive + CastAs<f64> + PartialEq + std::fmt::Debug,
+{
+    let report = explain::<S, T>(x);
+    assert_eq!(report.value, x.cast_as(), "{} as {}", x, T::NAME);
+
+    let fits = match T::CLASS {
+        Class::Float => true,
+        // 源值只来自 8 位类型，64 位以上的目标一定放得下。
+        Class::Signed => T::BITS >= 64 || (v >= -(1 << (T::BITS - 1)) && v < 1 << (T::BITS - 1)),
+        Class::Unsigned => v >= 0 && (T::BITS >= 64 || v < 1 << T::BITS),
+        Class::Char | Class::Bool => unreachable!("{} is not a target here", T::NAME),
+    };
+    let checked = checked_cast::<S, T>(x);
+    assert_eq!(checked.is_some(), fits, "{} as {}", x, T::NAME);
+    assert_eq!(report.kind == CastKind::Lossless, fits);
+    if let Some(c) = checked {
+        assert_eq!(c, report.value);
+        assert_eq!(c.cast_as(), v as f64);
+    }
+
+    // 整数源的 `as` 就是回绕转换。
+    assert_eq!(wrapping_cast::<S, T>(x), report.value);
+
+    let saturated: f64 = saturating_cast::<S, T>(x).cast_as();
+    if fits {
+        assert_eq!(saturated, v as f64);
+    } else if v < 0 {
+        assert!(saturated <= 0.0);
+    } else {
+        assert!(saturated > 0.0);
+    }
+
+    match report.kind {
+        CastKind::Lossless => {}
+        CastKind::Truncating => assert!(T::BITS < S::BITS),
+        CastKind::Wrapping => assert!(T::BITS >= S::BITS),
+        kind => panic!("unexpected {} for {} as {}", kind, x, T::NAME),
+    }
+}
+
+#[cfg(test)]
+macro_rules! check_all_targets {
+    ($x:expr, $v:expr) => {{
+        check_int_source::<_, i8>($x, $v);
+        check_int_source::<_, i16>($x, $v);
+        check_int_source::<_, i32>($x, $v);
+        check_int_source::<_, i64>($x, $v);
+        check_int_source::<_, i128>($x, $v);
+        check_int_source::<_, isize>($x, $v);
+        check_int_source::<_, u8>($x, $v);
+        check_int_source::<_, u16>($x, $v);
+        check_int_source::<_, u32>($x, $v);
+        check_int_source::<_, u64>($x, $v);
+        check_int_source::<_, u128>($x, $v);
+        check_int_source::<_, usize>($x, $v);
+        check_int_source::<_, f32>($x, $v);
+        check_int_source::<_, f64>($x, $v);
+    }};
+}
+
+#[test]
+fn exhaustive_u8() {
+    for x in u8::MIN..=u8::MAX {
+        check_all_targets!(x, x as i128);
+    }
+}
+
+#[test]
+fn exhaustive_i8() {
+    for x in i8::MIN..=i8::MAX {
+        check_all_targets!(x, x as i128);
+    }
+}
+
+#[test]
+fn char_and_bool() {
+    // `65.4321 as u8 as char`
+    let byte = explain::<_, u8>(65.4321f32).value;
+    let r = explain::<_, char>(byte);
+    assert_eq!((r.value, r.kind), ('A', CastKind::Lossless));
+    assert_eq!(r.explanation, "65u8 is the code point U+0041: 'A'");
+
+    let r = explain::<_, u32>('中');
+    assert_eq!((r.value, r.kind), (20013, CastKind::Lossless));
+    assert_eq!(r.explanation, "'中' is the code point U+4E2D: 20013u32");
+
+    let r = explain::<_, u8>('中');
+    assert_eq!((r.value, r.kind), (b'-', CastKind::Truncating));
+    assert_eq!(
+        r.explanation,
+        "'中' = 0b00000000000000000100111000101101, keep the low 8 bits 0b00101101 = 45u8"
+    );
+    assert_eq!(explain::<_, u8>('é').kind, CastKind::Lossless);
+    assert_eq!(explain::<_, i8>('é').kind, CastKind::Truncating);
+
+    let r = explain::<_, i32>(true);
+    assert_eq!((r.value, r.kind), (1, CastKind::Lossless));
+    assert_eq!(r.explanation, "true is stored as 1: 1i32");
+    assert_eq!(explain::<_, u128>(false).value, 0);
+
+    assert_eq!(checked_cast::<_, char>(0x4E2Du32), Some('中'));
+    assert_eq!(checked_cast::<_, char>(0xD800u32), None);
+    assert_eq!(checked_cast::<_, char>(-1i32), None);
+    assert_eq!(saturating_cast::<_, char>(1000i32), 'ÿ');
+    assert_eq!(wrapping_cast::<_, char>(0x4E2Du32), '-');
+
+    assert_eq!(checked_cast::<_, bool>(1u64), Some(true));
+    assert_eq!(checked_cast::<_, bool>(2u64), None);
+    assert!(saturating_cast::<_, bool>(2u64));
+    assert!(!wrapping_cast::<_, bool>(2u64));
+    assert_eq!(checked_cast::<_, u8>(true), Some(1));
+}
+
+#[test]
+fn exhaustive_u8_to_char() {
+    for x in u8::MIN..=u8::MAX {
+        let r = explain::<_, char>(x);
+        assert_eq!(r.value, x as char);
+        assert_eq!(r.kind, CastKind::Lossless);
+        assert_eq!(checked_cast::<_, u8>(r.value), Some(x));
+        assert_eq!(explain::<_, u8>(r.value).kind, CastKind::Lossless);
+    }
+}
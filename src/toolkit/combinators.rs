@@ -0,0 +1,230 @@
+/*!
+ * @Author: ZZX
+ * @Description: Option 与 Result 的组合子
+ * 扩展 `y_if_let::one`：那里用 `if let ... else if ... else` 一层层地处理 `Option<i32>`
+ * 和自定义的 `Foo`。这里给 `Option` 和 `Result` 补上标准库没有的几个组合子，
+ * 以及对任意枚举变体做 `if let` 的 `variant_of!`。只看一眼值的 `inspect`/`inspect_err`
+ * 标准库已经有了，这里只补上 `None` 时的 `inspect_none`。
+ * @Date: create in 2026/10/19 5:46 上午
+ */
+
+use std::fmt::{Display, Write};
+
+pub trait OptionExt<T>: Sized {
+    /// 是 `None` 时执行 `f`，原样返回。和标准库的 `Option::inspect` 正好相反。
+    fn inspect_none<F: FnOnce()>(self, f: F) -> Self;
+
+    /// 两个都是 `Some` 时用 `f` 合并，否则是 `None`。
+    /// 标准库里同样功能的 `Option::zip_with` 还不稳定，换个名字以免将来冲突。
+    fn zip_map<U, R, F: FnOnce(T, U) -> R>(self, other: Option<U>, f: F) -> Option<R>;
+
+    /// 是否是等于 `value` 的 `Some`。
+    fn has<U>(&self, value: &U) -> bool
+    where
+        T: PartialEq<U>;
+}
+
+impl<T> OptionExt<T> for Option<T> {
+    fn inspect_none<F: FnOnce()>(self, f: F) -> Self {
+        if self.is_none() {
+            f();
+        }
+        self
+    }
+
+    fn zip_map<U, R, F: FnOnce(T, U) -> R>(self, other: Option<U>, f: F) -> Option<R> {
+        match (self, other) {
+            (Some(a), Some(b)) => Some(f(a, b)),
+            _ => None,
+        }
+    }
+
+    fn has<U>(&self, value: &U) -> bool
+    where
+        T: PartialEq<U>,
+    {
+        matches!(self, Some(v) if v == value)
+    }
+}
+
+pub trait ResultExt<T, E>: Sized {
+    /// 转成 `Option`，丢掉错误之前把它以 `context: error` 的形式打印到标准错误。
+    fn ok_or_log(self, context: &str) -> Option<T>
+    where
+        E: Display;
+
+    /// 和 `ok_or_log` 一样，但日志写进 `out`，每条一行。
+    fn ok_or_log_to<W: Write>(self, context: &str, out: &mut W) -> Option<T>
+    where
+        E: Display;
+
+    /// 用 `f` 挽救一部分错误：`f` 返回 `Some` 时变成 `Ok`，返回 `None` 时保留原来的错误。
+    fn filter_map_err<F: FnOnce(&E) -> Option<T>>(self, f: F) -> Result<T, E>;
+
+    /// 两个都是 `Ok` 时用 `f` 合并，否则是第一个错误。
+    fn zip_map<U, R, F: FnOnce(T, U) -> R>(self, other: Result<U, E>, f: F) -> Result<R, E>;
+}
+
+impl<T, E> ResultExt<T, E> for Result<T, E> {
+    fn ok_or_log(self, context: &str) -> Option<T>
+    where
+        E: Display,
+    {
+        self.inspect_err(|e| eprintln!("{}: {}", context, e)).ok()
+    }
+
+    fn ok_or_log_to<W: Write>(self, context: &str, out: &mut W) -> Option<T>
+    where
+        E: Display,
+    {
+        // 写日志失败不影响结果。
+        self.inspect_err(|e| {
+            let _ = writeln!(out, "{}: {}", context, e);
+        })
+        .ok()
+    }
+
+    fn filter_map_err<F: FnOnce(&E) -> Option<T>>(self, f: F) -> Result<T, E> {
+        match self {
+            Ok(value) => Ok(value),
+            Err(e) => f(&e).ok_or(e),
+        }
+    }
+
+    fn zip_map<U, R, F: FnOnce(T, U) -> R>(self, other: Result<U, E>, f: F) -> Result<R, E> {
+        Ok(f(self?, other?))
+    }
+}
+
+/// 对任意枚举变体的 `if let`，写成表达式：
+///
+/// ```ignore
+/// variant_of!(c, Foo::Qux(value) => value)           // Option<u32>
+/// variant_of!(c, Foo::Qux(value) if value > 10 => value)
+/// variant_of!(a, Foo::Bar)                           // bool
+/// ```
+#[allow(unused_macros)]
+macro_rules! variant_of {
+    ($value:expr, $pattern:pat $(if $guard:expr)? => $output:expr) => {
+        match $value {
+            $pattern $(if $guard)? => Some($output),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    };
+    ($value:expr, $pattern:pat $(if $guard:expr)?) => {
+        matches!($value, $pattern $(if $guard)?)
+    };
+}
+
+#[allow(unused_imports)]
+pub(crate) use variant_of;
+
+#[cfg(test)]
+#[derive(Debug, Clone, PartialEq)]
+enum Foo {
+    Bar,
+    Baz,
+    Qux(u32),
+    Point { x: i32, y: i32 },
+}
+
+#[test]
+fn inspect_none_and_has() {
+    let mut misses = 0;
+    let letter: Option<i32> = None;
+    assert_eq!(letter.inspect_none(|| misses += 1), None);
+    assert_eq!(Some(1).inspect_none(|| misses += 1), Some(1));
+    assert_eq!(misses, 1);
+
+    assert!(Some(7).has(&7));
+    assert!(!Some(7).has(&8));
+    assert!(!None::<i32>.has(&7));
+    assert!(Some("abc".to_string()).has(&"abc"));
+}
+
+#[test]
+fn zipping() {
+    assert_eq!(Some(3).zip_map(Some(4), |a, b| a * b), Some(12));
+    assert_eq!(Some(3).zip_map(None::<i32>, |a, b| a * b), None);
+
+    let ok: Result<i32, &str> = Ok(2);
+    assert_eq!(
+        ok.zip_map(Ok("x"), |n, s| s.repeat(n as usize)),
+        Ok("xx".to_string())
+    );
+    assert_eq!(
+        Err::<i32, _>("first").zip_map(Err::<i32, _>("second"), |a, b| a + b),
+        Err("first")
+    );
+    assert_eq!(
+        Ok(1).zip_map(Err::<i32, _>("second"), |a, b| a + b),
+        Err("second")
+    );
+}
+
+#[test]
+fn logging_errors() {
+    let mut log = String::new();
+    let parsed: Vec<i32> = ["1", "two", "3"]
+        .iter()
+        .filter_map(|s| {
+            s.parse::<i32>()
+                .ok_or_log_to(&format!("parse {:?}", s), &mut log)
+        })
+        .collect();
+
+    assert_eq!(parsed, vec![1, 3]);
+    assert_eq!(log, "parse \"two\": invalid digit found in string\n");
+
+    assert_eq!("5".parse::<i32>().ok_or_log("parse"), Some(5));
+}
+
+#[test]
+fn recovering_some_errors() {
+    use std::num::IntErrorKind;
+
+    // 空字符串当作 0，其它错误保留。
+    let parse = |s: &str| {
+        s.parse::<i32>()
+            .filter_map_err(|e| (*e.kind() == IntErrorKind::Empty).then_some(0))
+    };
+    assert_eq!(parse("12"), Ok(12));
+    assert_eq!(parse(""), Ok(0));
+    assert_eq!(*parse("x").unwrap_err().kind(), IntErrorKind::InvalidDigit);
+}
+
+#[test]
+fn variant_of_patterns() {
+    let values = [
+        Foo::Bar,
+        Foo::Baz,
+        Foo::Qux(100),
+        Foo::Qux(3),
+        Foo::Point { x: 1, y: -1 },
+    ];
+
+    let quxes: Vec<u32> = values
+        .iter()
+        .filter_map(|v| variant_of!(v, Foo::Qux(n) => *n))
+        .collect();
+    assert_eq!(quxes, vec![100, 3]);
+
+    let big: Vec<u32> = values
+        .iter()
+        .filter_map(|v| variant_of!(v, Foo::Qux(n) if *n > 10 => *n))
+        .collect();
+    assert_eq!(big, vec![100]);
+
+    assert!(variant_of!(&values[0], Foo::Bar));
+    assert!(!variant_of!(&values[1], Foo::Bar));
+    assert!(variant_of!(&values[4], Foo::Point { x, .. } if *x > 0));
+    assert_eq!(
+        variant_of!(values[4].clone(), Foo::Point { x, y } => x + y),
+        Some(0)
+    );
+
+    // 也能用在 `Option` 和 `Result` 上。
+    assert_eq!(variant_of!(Some(7), Some(i) => i), Some(7));
+    assert_eq!(variant_of!(Err::<i32, _>("e"), Ok(i) => i), None);
+}
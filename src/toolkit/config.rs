@@ -0,0 +1,624 @@
+/*!
+ * @Author: ZZX
+ * @Description: 分层配置
+ * 替代 `q_constants` 里写死的 `LANGUAGE` 和 `THRESHOLD`：它们仍然是编译期的默认值，
+ * 但可以依次被本地配置文件、`RBE_*` 环境变量和运行时的命令行参数覆盖，
+ * 后面的层覆盖前面的层。每个值在写入时都会按它的类型校验。
+ * 通过 `cargo run -- config dump` 查看最终的配置以及每个值来自哪一层。
+ * @Date: create in 2026/10/19 5:46 上午
+ */
+
+use std::env;
+use std::ffi::OsString;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::io;
+use std::sync::OnceLock;
+
+use crate::example::q_constants::{LANGUAGE, THRESHOLD};
+
+/// 没有用 `--config` 指定时读取的配置文件，不存在也没关系。
+pub const DEFAULT_FILE: &str = "rbe.conf";
+/// 环境变量的前缀：`RBE_THRESHOLD=5` 覆盖 `threshold`。
+pub const ENV_PREFIX: &str = "RBE_";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    Str(String),
+    Int(i64),
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Str(s) => write!(f, "{:?}", s),
+            Value::Int(n) => write!(f, "{}", n),
+        }
+    }
+}
+
+/// 配置项的类型，同时也是它的校验规则。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// 非空字符串。
+    Str,
+    /// 闭区间 `min..=max` 内的整数。
+    Int { min: i64, max: i64 },
+}
+
+impl Kind {
+    fn parse(self, raw: &str) -> Option<Value> {
+        match self {
+            Kind::Str => {
+                let s = raw.trim();
+                if s.is_empty() {
+                    None
+                } else {
+                    Some(Value::Str(s.to_string()))
+                }
+            }
+            Kind::Int { min, max } => raw
+                .trim()
+                .parse::<i64>()
+                .ok()
+                .filter(|n| (min..=max).contains(n))
+                .map(Value::Int),
+        }
+    }
+}
+
+impl Display for Kind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Kind::Str => write!(f, "a non-empty string"),
+            Kind::Int { min, max } => write!(f, "an integer in {}..={}", min, max),
+        }
+    }
+}
+
+/// 值来自哪一层，后面的层优先。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Layer {
+    Default,
+    File,
+    Env,
+    Cli,
+}
+
+impl Display for Layer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Layer::Default => "default",
+            Layer::File => "file",
+            Layer::Env => "env",
+            Layer::Cli => "cli",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+pub struct Setting {
+    pub key: &'static str,
+    pub kind: Kind,
+    pub default: Value,
+}
+
+/// 所有配置项及其编译期默认值。
+pub fn settings() -> Vec<Setting> {
+    vec![
+        Setting {
+            key: "language",
+            kind: Kind::Str,
+            default: Value::Str(LANGUAGE.to_string()),
+        },
+        Setting {
+            key: "threshold",
+            kind: Kind::Int {
+                min: i32::MIN as i64,
+                max: i32::MAX as i64,
+            },
+            default: Value::Int(THRESHOLD as i64),
+        },
+    ]
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    UnknownKey {
+        key: String,
+        origin: String,
+    },
+    InvalidValue {
+        key: &'static str,
+        value: String,
+        expected: Kind,
+        origin: String,
+    },
+    /// 值不是合法的 UTF-8，例如某些环境变量。
+    NotUnicode {
+        origin: String,
+    },
+    /// 配置文件里不是 `key = value` 的行。
+    Syntax {
+        origin: String,
+        line: String,
+    },
+    Io {
+        path: String,
+        message: String,
+    },
+    /// 命令行参数用错了。
+    Usage(String),
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::UnknownKey { key, origin } => {
+                write!(f, "{}: unknown config key `{}`", origin, key)
+            }
+            ConfigError::InvalidValue {
+                key,
+                value,
+                expected,
+                origin,
+            } => write!(
+                f,
+                "{}: invalid value {:?} for `{}`, expected {}",
+                origin, value, key, expected
+            ),
+            ConfigError::NotUnicode { origin } => {
+                write!(f, "{}: value is not valid UTF-8", origin)
+            }
+            ConfigError::Syntax { origin, line } => {
+                write!(f, "{}: expected `key = value`, found {:?}", origin, line)
+            }
+            ConfigError::Io { path, message } => write!(f, "{}: {}", path, message),
+            ConfigError::Usage(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub key: &'static str,
+    pub value: Value,
+    pub layer: Layer,
+    /// 具体的来源，例如 `rbe.conf:3` 或 `RBE_THRESHOLD`。
+    pub origin: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    entries: Vec<Entry>,
+}
+
+impl Default for Config {
+    /// 只包含编译期默认值的配置。
+    fn default() -> Self {
+        let entries = settings()
+            .into_iter()
+            .map(|s| Entry {
+                key: s.key,
+                value: s.default,
+                layer: Layer::Default,
+                origin: "built-in".to_string(),
+            })
+            .collect();
+        Config { entries }
+    }
+}
+
+impl Config {
+    /// 按顺序叠加默认值、配置文件、环境变量和命令行的覆盖。
+    pub fn load(cli: &CliOverrides) -> Result<Config, ConfigError> {
+        let mut config = Config::default();
+
+        let (path, required) = match &cli.config_path {
+            Some(path) => (path.as_str(), true),
+            None => (DEFAULT_FILE, false),
+        };
+        match fs::read_to_string(path) {
+            Ok(text) => config.apply_file(&text, path)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound && !required => {}
+            Err(e) => {
+                return Err(ConfigError::Io {
+                    path: path.to_string(),
+                    message: e.to_string(),
+                })
+            }
+        }
+
+        // 环境变量不归我们管，不认识的 `RBE_*` 变量只警告，不影响运行。
+        for warning in config.apply_env(env::vars_os())? {
+            eprintln!("warning: {}, ignored", warning);
+        }
+        config.apply_cli(cli)?;
+        Ok(config)
+    }
+
+    /// 校验并写入一个值。
+    pub fn set(
+        &mut self,
+        key: &str,
+        raw: &str,
+        layer: Layer,
+        origin: &str,
+    ) -> Result<(), ConfigError> {
+        let kind = settings()
+            .into_iter()
+            .find(|s| s.key == key)
+            .map(|s| s.kind)
+            .ok_or_else(|| ConfigError::UnknownKey {
+                key: key.to_string(),
+                origin: origin.to_string(),
+            })?;
+        let entry = self.entries.iter_mut().find(|e| e.key == key).unwrap();
+        let value = kind.parse(raw).ok_or_else(|| ConfigError::InvalidValue {
+            key: entry.key,
+            value: raw.to_string(),
+            expected: kind,
+            origin: origin.to_string(),
+        })?;
+
+        entry.value = value;
+        entry.layer = layer;
+        entry.origin = origin.to_string();
+        Ok(())
+    }
+
+    /// 读取配置文件的内容：每行一个 `key = value`，`#` 开头的行是注释。
+    /// 值里的 `#` 是值的一部分。
+    pub fn apply_file(&mut self, text: &str, path: &str) -> Result<(), ConfigError> {
+        for (i, line) in text.lines().enumerate() {
+            let origin = format!("{}:{}", path, i + 1);
+            let content = line.trim();
+            if content.is_empty() || content.starts_with('#') {
+                continue;
+            }
+            let (key, value) = content.split_once('=').ok_or_else(|| ConfigError::Syntax {
+                origin: origin.clone(),
+                line: line.to_string(),
+            })?;
+            self.set(key.trim(), value, Layer::File, &origin)?;
+        }
+        Ok(())
+    }
+
+    /// 只处理带 `RBE_` 前缀的变量，其余的忽略。不认识的 `RBE_*` 变量和不是 UTF-8 的值
+    /// 会被跳过并作为警告返回；认识的变量的值不合法时仍然是错误。
+    pub fn apply_env<I, K, V>(&mut self, vars: I) -> Result<Vec<ConfigError>, ConfigError>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<OsString>,
+        V: Into<OsString>,
+    {
+        let mut warnings = Vec::new();
+        for (name, value) in vars {
+            // 名字不是 UTF-8 的变量不可能是我们的。
+            let name = match name.into().into_string() {
+                Ok(name) => name,
+                Err(_) => continue,
+            };
+            let key = match name.strip_prefix(ENV_PREFIX) {
+                Some(key) => key.to_lowercase(),
+                None => continue,
+            };
+            if !settings().iter().any(|s| s.key == key) {
+                warnings.push(ConfigError::UnknownKey { key, origin: name });
+                continue;
+            }
+            match value.into().into_string() {
+                Ok(value) => self.set(&key, &value, Layer::Env, &name)?,
+                Err(_) => warnings.push(ConfigError::NotUnicode { origin: name }),
+            }
+        }
+        Ok(warnings)
+    }
+
+    pub fn apply_cli(&mut self, cli: &CliOverrides) -> Result<(), ConfigError> {
+        for (key, value) in &cli.sets {
+            self.set(key, value, Layer::Cli, &format!("--set {}", key))?;
+        }
+        Ok(())
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Entry> {
+        self.entries.iter().find(|e| e.key == key)
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    fn str_value(&self, key: &str) -> &str {
+        match self.get(key).map(|e| &e.value) {
+            Some(Value::Str(s)) => s,
+            _ => unreachable!("`{}` is a string setting", key),
+        }
+    }
+
+    fn int_value(&self, key: &str) -> i64 {
+        match self.get(key).map(|e| &e.value) {
+            Some(Value::Int(n)) => *n,
+            _ => unreachable!("`{}` is an integer setting", key),
+        }
+    }
+
+    pub fn language(&self) -> &str {
+        self.str_value("language")
+    }
+
+    pub fn threshold(&self) -> i32 {
+        // 写入时已经检查过范围。
+        self.int_value("threshold") as i32
+    }
+
+    /// `config dump` 的输出：每行一个配置项、它的值和来源。
+    pub fn dump(&self) -> String {
+        self.entries
+            .iter()
+            .map(|e| {
+                format!(
+                    "{:<10} = {:<8} # {} ({})",
+                    e.key,
+                    e.value.to_string(),
+                    e.layer,
+                    e.origin
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// 从命令行里取出的配置参数。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CliOverrides {
+    pub config_path: Option<String>,
+    pub sets: Vec<(String, String)>,
+}
+
+/// 把 `--config FILE` 和 `--set key=value` 从参数中分离出来，返回剩下的参数。
+pub fn split_args(args: &[String]) -> Result<(CliOverrides, Vec<String>), ConfigError> {
+    let mut cli = CliOverrides::default();
+    let mut rest = Vec::new();
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--config" => {
+                let path = iter.next().ok_or_else(|| {
+                    ConfigError::Usage("`--config` needs a file path".to_string())
+                })?;
+                cli.config_path = Some(path.clone());
+            }
+            "--set" => {
+                let pair = iter
+                    .next()
+                    .ok_or_else(|| ConfigError::Usage("`--set` needs `key=value`".to_string()))?;
+                let (key, value) = pair.split_once('=').ok_or_else(|| {
+                    ConfigError::Usage(format!("`--set {}` is not `key=value`", pair))
+                })?;
+                cli.sets.push((key.trim().to_string(), value.to_string()));
+            }
+            _ => rest.push(arg.clone()),
+        }
+    }
+    Ok((cli, rest))
+}
+
+static GLOBAL: OnceLock<Config> = OnceLock::new();
+
+/// 运行时使用的配置。没有调用过 `init_from_args`（例如在测试里）时只有默认值。
+pub fn global() -> &'static Config {
+    GLOBAL.get_or_init(Config::default)
+}
+
+/// 由 `main` 调用：加载配置并设为全局配置，返回去掉配置参数之后的参数。
+pub fn init_from_args(args: &[String]) -> Result<Vec<String>, ConfigError> {
+    let (cli, rest) = split_args(args)?;
+    let config = Config::load(&cli)?;
+    if GLOBAL.set(config).is_err() {
+        return Err(ConfigError::Usage(
+            "configuration is already initialized".to_string(),
+        ));
+    }
+    Ok(rest)
+}
+
+/// `config` 子命令，目前只有 `config dump`。
+pub fn run(args: &[String]) -> Result<(), String> {
+    match args.first().map(String::as_str) {
+        Some("dump") if args.len() == 1 => {
+            println!("{}", global().dump());
+            Ok(())
+        }
+        _ => Err("usage: config dump".to_string()),
+    }
+}
+
+#[cfg(test)]
+fn strings(args: &[&str]) -> Vec<String> {
+    args.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn defaults_come_from_q_constants() {
+    let config = Config::default();
+    assert_eq!(config.language(), LANGUAGE);
+    assert_eq!(config.threshold(), THRESHOLD);
+    assert!(config.entries().iter().all(|e| e.layer == Layer::Default));
+}
+
+#[test]
+fn later_layers_win() {
+    let mut config = Config::default();
+    config
+        .apply_file(
+            "# local settings\nlanguage = Ferris\n  # small\nthreshold = 3\n",
+            "rbe.conf",
+        )
+        .unwrap();
+    config
+        .apply_env(vec![
+            ("RBE_THRESHOLD".to_string(), "7".to_string()),
+            ("HOME".to_string(), "/root".to_string()),
+        ])
+        .unwrap();
+    let (cli, _) = split_args(&strings(&["--set", "threshold=42"])).unwrap();
+    config.apply_cli(&cli).unwrap();
+
+    assert_eq!(config.language(), "Ferris");
+    assert_eq!(config.threshold(), 42);
+
+    let language = config.get("language").unwrap();
+    assert_eq!(
+        (language.layer, language.origin.as_str()),
+        (Layer::File, "rbe.conf:2")
+    );
+    assert_eq!(config.get("threshold").unwrap().layer, Layer::Cli);
+
+    // 只有整行的注释，值里的 `#` 会保留下来。
+    config.apply_file("language = C#\n", "rbe.conf").unwrap();
+    assert_eq!(config.language(), "C#");
+}
+
+#[test]
+fn validation_errors() {
+    let mut config = Config::default();
+
+    assert_eq!(
+        config.set("threshold", "ten", Layer::Cli, "--set threshold"),
+        Err(ConfigError::InvalidValue {
+            key: "threshold",
+            value: "ten".to_string(),
+            expected: Kind::Int {
+                min: i32::MIN as i64,
+                max: i32::MAX as i64
+            },
+            origin: "--set threshold".to_string(),
+        })
+    );
+    assert!(config
+        .set("threshold", "99999999999", Layer::Cli, "--set threshold")
+        .is_err());
+    assert!(config
+        .set("language", "  ", Layer::Cli, "--set language")
+        .is_err());
+
+    let err = config
+        .apply_env(vec![("RBE_THRESHOLD", "many")])
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .starts_with("RBE_THRESHOLD: invalid value \"many\" for `threshold`"));
+
+    let err = config
+        .apply_file("threshold = 1\njust words\n", "my.conf")
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "my.conf:2: expected `key = value`, found \"just words\""
+    );
+
+    // 失败的写入不会改动已有的值。
+    assert_eq!(config.threshold(), 1);
+    assert_eq!(config, {
+        let mut expected = Config::default();
+        expected
+            .set("threshold", "1", Layer::File, "my.conf:1")
+            .unwrap();
+        expected
+    });
+}
+
+#[test]
+fn unrelated_env_vars_are_warnings() {
+    let mut config = Config::default();
+    let warnings = config
+        .apply_env(vec![
+            ("RBE_COLOUR", "red"),
+            ("RBE_THRESHOLD", "8"),
+            ("PATH", "/bin"),
+        ])
+        .unwrap();
+    assert_eq!(
+        warnings,
+        [ConfigError::UnknownKey {
+            key: "colour".to_string(),
+            origin: "RBE_COLOUR".to_string()
+        }]
+    );
+    assert_eq!(
+        warnings[0].to_string(),
+        "RBE_COLOUR: unknown config key `colour`"
+    );
+    assert_eq!(config.threshold(), 8);
+}
+
+#[cfg(unix)]
+#[test]
+fn non_unicode_env_vars_are_skipped() {
+    use std::os::unix::ffi::OsStringExt;
+
+    let invalid = || OsString::from_vec(vec![b'a', 0xff]);
+    let mut config = Config::default();
+    let warnings = config
+        .apply_env(vec![
+            (invalid(), OsString::from("1")),
+            (OsString::from("RBE_LANGUAGE"), invalid()),
+            (OsString::from("RBE_THRESHOLD"), OsString::from("9")),
+        ])
+        .unwrap();
+    assert_eq!(
+        warnings,
+        [ConfigError::NotUnicode {
+            origin: "RBE_LANGUAGE".to_string()
+        }]
+    );
+    assert_eq!(config.language(), LANGUAGE);
+    assert_eq!(config.threshold(), 9);
+}
+
+#[test]
+fn command_line_splitting() {
+    let (cli, rest) = split_args(&strings(&[
+        "--config",
+        "dev.conf",
+        "config",
+        "--set",
+        "language=C",
+        "dump",
+    ]))
+    .unwrap();
+    assert_eq!(cli.config_path.as_deref(), Some("dev.conf"));
+    assert_eq!(cli.sets, vec![("language".to_string(), "C".to_string())]);
+    assert_eq!(rest, strings(&["config", "dump"]));
+
+    assert!(split_args(&strings(&["--set"])).is_err());
+    assert!(split_args(&strings(&["--set", "threshold"])).is_err());
+    assert!(split_args(&strings(&["layout", "--config"])).is_err());
+
+    // 显式指定的配置文件必须存在。
+    let cli = CliOverrides {
+        config_path: Some("/nonexistent/rbe.conf".to_string()),
+        sets: Vec::new(),
+    };
+    assert!(matches!(Config::load(&cli), Err(ConfigError::Io { .. })));
+}
+
+#[test]
+fn dump_format() {
+    let mut config = Config::default();
+    config
+        .set("threshold", "5", Layer::Env, "RBE_THRESHOLD")
+        .unwrap();
+    assert_eq!(
+        config.dump(),
+        "language   = \"RUST\"   # default (built-in)\n\
+         threshold  = 5        # env (RBE_THRESHOLD)"
+    );
+    assert!(run(&strings(&["dump"])).is_ok());
+    assert!(run(&strings(&["show"])).is_err());
+}
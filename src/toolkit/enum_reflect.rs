@@ -0,0 +1,204 @@
+/*!
+ * @Author: ZZX
+ * @Description: 枚举反射
+ * 扩展 `o_c_language_style`：那里的 `Number` 和 `Color` 只能用 `as i32` 转成整数。
+ * `reflect_enum!` 包住一个 C 风格的枚举定义，额外生成所有变体的列表、
+ * 名字与变体的互相转换、从辨别值的 `TryFrom<i32>`、`FromStr`、`Display`
+ * 以及遍历所有变体的迭代器。
+ * @Date: create in 2026/10/19 5:46 上午
+ */
+
+use std::fmt::{self, Display, Formatter};
+
+/// 由 `reflect_enum!` 实现的反射接口。
+pub trait EnumReflect: Sized + Copy + 'static {
+    /// 枚举类型的名字。
+    const NAME: &'static str;
+    /// 按声明顺序排列的所有变体。
+    const VARIANTS: &'static [Self];
+
+    fn name(self) -> &'static str;
+    fn discriminant(self) -> i32;
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::iter().find(|v| v.name() == name)
+    }
+
+    fn from_discriminant(value: i32) -> Option<Self> {
+        Self::iter().find(|v| v.discriminant() == value)
+    }
+
+    fn iter() -> std::iter::Copied<std::slice::Iter<'static, Self>> {
+        Self::VARIANTS.iter().copied()
+    }
+}
+
+/// `FromStr` 和 `TryFrom<i32>` 失败时的错误。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReflectError {
+    UnknownName {
+        enum_name: &'static str,
+        name: String,
+    },
+    UnknownDiscriminant {
+        enum_name: &'static str,
+        value: i32,
+    },
+}
+
+impl Display for ReflectError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ReflectError::UnknownName { enum_name, name } => {
+                write!(f, "`{}` is not a variant of {}", name, enum_name)
+            }
+            ReflectError::UnknownDiscriminant { enum_name, value } => {
+                write!(f, "{} is not a discriminant of {}", value, enum_name)
+            }
+        }
+    }
+}
+
+/// 定义一个 C 风格的枚举并为它实现 `EnumReflect`、`Display`、`FromStr` 和 `TryFrom<i32>`。
+///
+/// 生成的枚举自动派生 `Debug, Clone, Copy, PartialEq, Eq`。
+macro_rules! reflect_enum {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $($(#[$vmeta:meta])* $variant:ident $(= $value:expr)?),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        $vis enum $name {
+            $($(#[$vmeta])* $variant $(= $value)?),*
+        }
+
+        impl $crate::toolkit::enum_reflect::EnumReflect for $name {
+            const NAME: &'static str = stringify!($name);
+            const VARIANTS: &'static [Self] = &[$($name::$variant),*];
+
+            fn name(self) -> &'static str {
+                match self {
+                    $($name::$variant => stringify!($variant)),*
+                }
+            }
+
+            fn discriminant(self) -> i32 {
+                self as i32
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                use $crate::toolkit::enum_reflect::EnumReflect;
+                write!(f, "{}", self.name())
+            }
+        }
+
+        impl std::str::FromStr for $name {
+            type Err = $crate::toolkit::enum_reflect::ReflectError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                use $crate::toolkit::enum_reflect::{EnumReflect, ReflectError};
+                Self::from_name(s).ok_or_else(|| ReflectError::UnknownName {
+                    enum_name: Self::NAME,
+                    name: s.to_string(),
+                })
+            }
+        }
+
+        impl std::convert::TryFrom<i32> for $name {
+            type Error = $crate::toolkit::enum_reflect::ReflectError;
+
+            fn try_from(value: i32) -> Result<Self, Self::Error> {
+                use $crate::toolkit::enum_reflect::{EnumReflect, ReflectError};
+                Self::from_discriminant(value).ok_or(ReflectError::UnknownDiscriminant {
+                    enum_name: Self::NAME,
+                    value,
+                })
+            }
+        }
+    };
+}
+
+pub(crate) use reflect_enum;
+
+#[cfg(test)]
+mod fixtures {
+    reflect_enum! {
+        pub enum Empty {}
+    }
+
+    reflect_enum! {
+        /// 辨别值可以只写一部分，其余的依次递增。
+        pub enum Level {
+            Low = -1,
+            Mid,
+            #[allow(dead_code)]
+            High = 10,
+        }
+    }
+}
+
+#[test]
+fn chapter_enums() {
+    use crate::example::n_use::{Status, Work};
+    use crate::example::o_c_language_style::{Color, Number};
+    use std::convert::TryFrom;
+
+    assert_eq!(Number::VARIANTS, &[Number::Zero, Number::One, Number::Two]);
+    assert_eq!(Number::Two.name(), "Two");
+    assert_eq!(Number::try_from(1), Ok(Number::One));
+
+    assert_eq!(Color::Red.discriminant(), 0xff0000);
+    assert_eq!(Color::try_from(0x00ff00), Ok(Color::Green));
+    assert_eq!(
+        Color::try_from(0x123456),
+        Err(ReflectError::UnknownDiscriminant {
+            enum_name: "Color",
+            value: 0x123456
+        })
+    );
+    assert_eq!("Blue".parse::<Color>(), Ok(Color::Blue));
+    assert_eq!(Color::Blue.to_string(), "Blue");
+
+    assert_eq!(Status::from_name("Rich"), Some(Status::Rich));
+    assert_eq!(Status::from_name("rich"), None);
+    let names: Vec<&str> = Work::iter().map(Work::name).collect();
+    assert_eq!(names, vec!["Civilian", "Soldier"]);
+}
+
+#[test]
+fn parse_errors_and_edge_cases() {
+    use fixtures::{Empty, Level};
+    use std::convert::TryFrom;
+
+    let err = "Purple".parse::<Level>().unwrap_err();
+    assert_eq!(err.to_string(), "`Purple` is not a variant of Level");
+    assert_eq!(
+        Level::try_from(5).unwrap_err().to_string(),
+        "5 is not a discriminant of Level"
+    );
+
+    let values: Vec<i32> = Level::iter().map(Level::discriminant).collect();
+    assert_eq!(values, vec![-1, 0, 10]);
+    assert_eq!(Level::try_from(0), Ok(Level::Mid));
+
+    assert_eq!(Empty::VARIANTS.len(), 0);
+    assert_eq!(Empty::iter().count(), 0);
+    assert!("anything".parse::<Empty>().is_err());
+}
+
+#[test]
+fn round_trips_for_every_variant() {
+    use crate::example::o_c_language_style::Color;
+    use std::convert::TryFrom;
+
+    for color in Color::iter() {
+        assert_eq!(color.to_string().parse::<Color>(), Ok(color));
+        assert_eq!(Color::try_from(color.discriminant()), Ok(color));
+        assert_eq!(Color::from_name(color.name()), Some(color));
+    }
+}
@@ -0,0 +1,685 @@
+/*!
+ * @Author: ZZX
+ * @Description: 农场模拟
+ * 扩展 `zt_trait::one`：那里只有一只会被剪毛的 `Sheep`，`Animal` 也只能说话。
+ * 这里的 `Animal` 多了随时间变化的状态：每过一天都会变饿，绵羊的毛会长回来，
+ * 奶牛会产奶，母鸡会下蛋。`Farm` 用 `Vec<Box<dyn Animal>>` 养着不同种类的动物，
+ * 提供剪毛、喂食、挤奶等操作，发生的每件事都按顺序记进日志，同样的操作总是得到同样的日志。
+ * @Date: create in 2026/10/19 5:46 上午
+ */
+
+use std::fmt::{self, Display, Formatter};
+
+use crate::toolkit::animal_factory::{AnimalRegistry, FactoryError};
+
+/// 饥饿值达到这里时开始饿，不再长毛、产奶、下蛋。
+pub const HUNGRY: u32 = 10;
+/// 饥饿值的上限。
+pub const STARVING: u32 = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventKind {
+    Hungry,
+    Starving,
+    /// `hunger` 是喂完之后的饥饿值。
+    Fed {
+        amount: u32,
+        hunger: u32,
+    },
+    Sheared {
+        wool: u32,
+    },
+    AlreadyNaked,
+    WoolRegrown,
+    Milked {
+        liters: u32,
+    },
+    NoMilk,
+    LaidEgg,
+}
+
+impl Display for EventKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            EventKind::Hungry => write!(f, "is hungry"),
+            EventKind::Starving => write!(f, "is starving"),
+            EventKind::Fed { amount, hunger } => {
+                write!(f, "was fed {} (hunger {})", amount, hunger)
+            }
+            EventKind::Sheared { wool } => write!(f, "gets a haircut ({} wool)", wool),
+            EventKind::AlreadyNaked => write!(f, "is already naked"),
+            EventKind::WoolRegrown => write!(f, "has grown its wool back"),
+            EventKind::Milked { liters } => write!(f, "gave {} liters of milk", liters),
+            EventKind::NoMilk => write!(f, "has no milk yet"),
+            EventKind::LaidEgg => write!(f, "laid an egg"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    pub day: u32,
+    pub animal: String,
+    pub kind: EventKind,
+}
+
+impl Display for Event {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "day {}: {} {}", self.day, self.animal, self.kind)
+    }
+}
+
+/// 每种动物都有的饥饿值。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Hunger {
+    level: u32,
+}
+
+impl Hunger {
+    pub fn level(&self) -> u32 {
+        self.level
+    }
+
+    pub fn is_hungry(&self) -> bool {
+        self.level >= HUNGRY
+    }
+
+    /// 饥饿值增加 `rate`，刚跨过某个阈值时返回对应的事件。
+    fn grow(&mut self, rate: u32) -> Option<EventKind> {
+        let before = self.level;
+        self.level = (self.level + rate).min(STARVING);
+        if before < STARVING && self.level == STARVING {
+            Some(EventKind::Starving)
+        } else if before < HUNGRY && self.level >= HUNGRY {
+            Some(EventKind::Hungry)
+        } else {
+            None
+        }
+    }
+
+    fn feed(&mut self, amount: u32) -> EventKind {
+        self.level = self.level.saturating_sub(amount);
+        EventKind::Fed {
+            amount,
+            hunger: self.level,
+        }
+    }
+}
+
+pub trait Animal {
+    fn new(name: &str) -> Self
+    where
+        Self: Sized;
+
+    fn name(&self) -> &str;
+
+    /// 种类，例如 `"sheep"`。
+    fn kind(&self) -> &'static str;
+
+    fn noise(&self) -> &'static str;
+
+    fn talk(&self) -> String {
+        format!("{} says {}", self.name(), self.noise())
+    }
+
+    fn hunger(&self) -> &Hunger;
+
+    fn hunger_mut(&mut self) -> &mut Hunger;
+
+    /// 每天增加的饥饿值。
+    fn appetite(&self) -> u32;
+
+    fn grow_hungrier(&mut self) -> Option<EventKind> {
+        let appetite = self.appetite();
+        self.hunger_mut().grow(appetite)
+    }
+
+    /// 过了一天，返回这一天里发生的事。默认只会变饿。
+    fn tick(&mut self) -> Vec<EventKind> {
+        self.grow_hungrier().into_iter().collect()
+    }
+
+    fn feed(&mut self, amount: u32) -> EventKind {
+        self.hunger_mut().feed(amount)
+    }
+
+    /// 不能剪毛的动物返回 `None`。
+    fn shear(&mut self) -> Option<EventKind> {
+        None
+    }
+
+    /// 不能挤奶的动物返回 `None`。
+    fn milk(&mut self) -> Option<EventKind> {
+        None
+    }
+}
+
+pub struct Sheep {
+    name: String,
+    naked: bool,
+    /// 剪毛之后过了几天。
+    days_naked: u32,
+    hunger: Hunger,
+}
+
+impl Sheep {
+    /// 剪一次得到的羊毛。
+    pub const WOOL: u32 = 3;
+    /// 不饿的情况下，毛要长这么多天才能长回来。
+    pub const REGROWTH_DAYS: u32 = 3;
+
+    pub fn is_naked(&self) -> bool {
+        self.naked
+    }
+}
+
+impl Animal for Sheep {
+    fn new(name: &str) -> Sheep {
+        Sheep {
+            name: name.to_owned(),
+            naked: false,
+            days_naked: 0,
+            hunger: Hunger::default(),
+        }
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn kind(&self) -> &'static str {
+        "sheep"
+    }
+
+    fn noise(&self) -> &'static str {
+        if self.is_naked() {
+            "baaaaah?"
+        } else {
+            "baaaaah!"
+        }
+    }
+
+    fn talk(&self) -> String {
+        format!("{} pauses briefly... {}", self.name, self.noise())
+    }
+
+    fn hunger(&self) -> &Hunger {
+        &self.hunger
+    }
+
+    fn hunger_mut(&mut self) -> &mut Hunger {
+        &mut self.hunger
+    }
+
+    fn appetite(&self) -> u32 {
+        2
+    }
+
+    fn tick(&mut self) -> Vec<EventKind> {
+        let mut events: Vec<EventKind> = self.grow_hungrier().into_iter().collect();
+        if self.naked && !self.hunger.is_hungry() {
+            self.days_naked += 1;
+            if self.days_naked == Self::REGROWTH_DAYS {
+                self.naked = false;
+                events.push(EventKind::WoolRegrown);
+            }
+        }
+        events
+    }
+
+    fn shear(&mut self) -> Option<EventKind> {
+        if self.naked {
+            return Some(EventKind::AlreadyNaked);
+        }
+        self.naked = true;
+        self.days_naked = 0;
+        Some(EventKind::Sheared { wool: Self::WOOL })
+    }
+}
+
+pub struct Cow {
+    name: String,
+    liters: u32,
+    hunger: Hunger,
+}
+
+impl Cow {
+    pub const LITERS_PER_DAY: u32 = 2;
+    pub const MAX_LITERS: u32 = 10;
+}
+
+impl Animal for Cow {
+    fn new(name: &str) -> Cow {
+        Cow {
+            name: name.to_owned(),
+            liters: 0,
+            hunger: Hunger::default(),
+        }
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn kind(&self) -> &'static str {
+        "cow"
+    }
+
+    fn noise(&self) -> &'static str {
+        "moooooo!"
+    }
+
+    fn hunger(&self) -> &Hunger {
+        &self.hunger
+    }
+
+    fn hunger_mut(&mut self) -> &mut Hunger {
+        &mut self.hunger
+    }
+
+    fn appetite(&self) -> u32 {
+        3
+    }
+
+    fn tick(&mut self) -> Vec<EventKind> {
+        let events = self.grow_hungrier().into_iter().collect();
+        if !self.hunger.is_hungry() {
+            self.liters = (self.liters + Self::LITERS_PER_DAY).min(Self::MAX_LITERS);
+        }
+        events
+    }
+
+    fn milk(&mut self) -> Option<EventKind> {
+        Some(match std::mem::take(&mut self.liters) {
+            0 => EventKind::NoMilk,
+            liters => EventKind::Milked { liters },
+        })
+    }
+}
+
+pub struct Chicken {
+    name: String,
+    days_since_egg: u32,
+    hunger: Hunger,
+}
+
+impl Chicken {
+    /// 不饿的情况下每隔这么多天下一个蛋。
+    pub const DAYS_PER_EGG: u32 = 2;
+}
+
+impl Animal for Chicken {
+    fn new(name: &str) -> Chicken {
+        Chicken {
+            name: name.to_owned(),
+            days_since_egg: 0,
+            hunger: Hunger::default(),
+        }
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn kind(&self) -> &'static str {
+        "chicken"
+    }
+
+    fn noise(&self) -> &'static str {
+        "cluck!"
+    }
+
+    fn hunger(&self) -> &Hunger {
+        &self.hunger
+    }
+
+    fn hunger_mut(&mut self) -> &mut Hunger {
+        &mut self.hunger
+    }
+
+    fn appetite(&self) -> u32 {
+        1
+    }
+
+    fn tick(&mut self) -> Vec<EventKind> {
+        let mut events: Vec<EventKind> = self.grow_hungrier().into_iter().collect();
+        if !self.hunger.is_hungry() {
+            self.days_since_egg += 1;
+            if self.days_since_egg == Self::DAYS_PER_EGG {
+                self.days_since_egg = 0;
+                events.push(EventKind::LaidEgg);
+            }
+        }
+        events
+    }
+}
+
+/// 农场到目前为止的收成。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Produce {
+    pub wool: u32,
+    pub milk: u32,
+    pub eggs: u32,
+}
+
+impl Produce {
+    fn record(&mut self, kind: EventKind) {
+        match kind {
+            EventKind::Sheared { wool } => self.wool += wool,
+            EventKind::Milked { liters } => self.milk += liters,
+            EventKind::LaidEgg => self.eggs += 1,
+            _ => {}
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FarmError {
+    NoSuchAnimal(String),
+    /// 对不支持的动物执行了某个操作，例如给奶牛剪毛。
+    Unsupported {
+        animal: String,
+        kind: &'static str,
+        action: &'static str,
+    },
+}
+
+impl Display for FarmError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            FarmError::NoSuchAnimal(name) => write!(f, "there is no animal named `{}`", name),
+            FarmError::Unsupported {
+                animal,
+                kind,
+                action,
+            } => write!(f, "cannot {} {}, it is a {}", action, animal, kind),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Farm {
+    day: u32,
+    animals: Vec<Box<dyn Animal>>,
+    log: Vec<Event>,
+    produce: Produce,
+}
+
+impl Farm {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add<A: Animal + 'static>(&mut self, animal: A) {
+        self.add_boxed(Box::new(animal));
+    }
+
+    pub fn add_boxed(&mut self, animal: Box<dyn Animal>) {
+        self.animals.push(animal);
+    }
+
+    pub fn day(&self) -> u32 {
+        self.day
+    }
+
+    pub fn animals(&self) -> impl Iterator<Item = &dyn Animal> + '_ {
+        self.animals.iter().map(|a| a.as_ref())
+    }
+
+    pub fn find(&self, name: &str) -> Option<&dyn Animal> {
+        self.animals().find(|a| a.name() == name)
+    }
+
+    pub fn log(&self) -> &[Event] {
+        &self.log
+    }
+
+    pub fn produce(&self) -> Produce {
+        self.produce
+    }
+
+    /// 每只动物说一句话。
+    pub fn chorus(&self) -> Vec<String> {
+        self.animals().map(|a| a.talk()).collect()
+    }
+
+    fn record(&mut self, animal: String, kind: EventKind) {
+        self.produce.record(kind);
+        self.log.push(Event {
+            day: self.day,
+            animal,
+            kind,
+        });
+    }
+
+    /// 过一天，按动物加入农场的顺序记录发生的事。
+    pub fn step(&mut self) {
+        self.day += 1;
+        let mut events = Vec::new();
+        for animal in &mut self.animals {
+            for kind in animal.tick() {
+                events.push((animal.name().to_owned(), kind));
+            }
+        }
+        for (animal, kind) in events {
+            self.record(animal, kind);
+        }
+    }
+
+    pub fn run(&mut self, days: u32) {
+        for _ in 0..days {
+            self.step();
+        }
+    }
+
+    fn act<F>(&mut self, name: &str, action: &'static str, f: F) -> Result<EventKind, FarmError>
+    where
+        F: FnOnce(&mut dyn Animal) -> Option<EventKind>,
+    {
+        let animal = self
+            .animals
+            .iter_mut()
+            .find(|a| a.name() == name)
+            .ok_or_else(|| FarmError::NoSuchAnimal(name.to_owned()))?;
+        let kind = f(animal.as_mut()).ok_or_else(|| FarmError::Unsupported {
+            animal: name.to_owned(),
+            kind: animal.kind(),
+            action,
+        })?;
+        self.record(name.to_owned(), kind);
+        Ok(kind)
+    }
+
+    pub fn feed(&mut self, name: &str, amount: u32) -> Result<EventKind, FarmError> {
+        self.act(name, "feed", |a| Some(a.feed(amount)))
+    }
+
+    pub fn shear(&mut self, name: &str) -> Result<EventKind, FarmError> {
+        self.act(name, "shear", |a| a.shear())
+    }
+
+    pub fn milk(&mut self, name: &str) -> Result<EventKind, FarmError> {
+        self.act(name, "milk", |a| a.milk())
+    }
+
+    /// 给所有饿了的动物喂 `amount`，返回喂了几只。
+    pub fn feed_hungry(&mut self, amount: u32) -> usize {
+        let hungry: Vec<String> = self
+            .animals()
+            .filter(|a| a.hunger().is_hungry())
+            .map(|a| a.name().to_owned())
+            .collect();
+        for name in &hungry {
+            self.feed(name, amount).expect("every animal can be fed");
+        }
+        hungry.len()
+    }
+
+    /// 给所有能剪毛的动物剪毛，返回这次得到的羊毛。
+    pub fn shear_all(&mut self) -> u32 {
+        let before = self.produce.wool;
+        let names: Vec<String> = self.animals().map(|a| a.name().to_owned()).collect();
+        for name in &names {
+            // 不能剪毛的动物直接跳过。
+            let _ = self.shear(name);
+        }
+        self.produce.wool - before
+    }
+}
+
+/// 把农场里的三种动物登记到 `registry`，都需要 `name`，绵羊还可以给 `naked=true`。
+pub fn register(registry: &mut AnimalRegistry) -> Result<(), FactoryError> {
+    registry.register("sheep", "grows wool, can be sheared", |attrs| {
+        let mut sheep = Sheep::new(&attrs.require("name")?);
+        if attrs.take_parsed("naked")?.unwrap_or(false) {
+            sheep.shear();
+        }
+        Ok(Box::new(sheep))
+    })?;
+    registry.register("cow", "gives milk", |attrs| {
+        Ok(Box::new(Cow::new(&attrs.require("name")?)))
+    })?;
+    registry.register("chicken", "lays eggs", |attrs| {
+        Ok(Box::new(Chicken::new(&attrs.require("name")?)))
+    })
+}
+
+#[cfg(test)]
+fn lines(farm: &Farm) -> Vec<String> {
+    farm.log().iter().map(Event::to_string).collect()
+}
+
+#[test]
+fn sheep_wool_regrows_unless_hungry() {
+    let mut dolly: Sheep = Animal::new("Dolly");
+    assert_eq!(dolly.talk(), "Dolly pauses briefly... baaaaah!");
+    assert_eq!(dolly.shear(), Some(EventKind::Sheared { wool: 3 }));
+    assert_eq!(dolly.shear(), Some(EventKind::AlreadyNaked));
+    assert_eq!(dolly.noise(), "baaaaah?");
+
+    assert_eq!(dolly.tick(), []);
+    assert_eq!(dolly.tick(), []);
+    assert_eq!(dolly.tick(), [EventKind::WoolRegrown]);
+    assert!(!dolly.is_naked());
+
+    // 饿着的时候毛不会长。
+    dolly.shear();
+    assert_eq!(dolly.tick(), []);
+    assert_eq!(dolly.tick(), [EventKind::Hungry]);
+    assert_eq!(dolly.hunger().level(), 10);
+    assert_eq!(dolly.tick(), []);
+    assert!(dolly.is_naked());
+    assert_eq!(
+        dolly.feed(4),
+        EventKind::Fed {
+            amount: 4,
+            hunger: 8
+        }
+    );
+    assert_eq!(dolly.tick(), [EventKind::Hungry]);
+    // 吃饱之后接着之前的天数长。
+    dolly.feed(10);
+    assert_eq!(dolly.tick(), []);
+    assert_eq!(dolly.tick(), [EventKind::WoolRegrown]);
+}
+
+#[test]
+fn hunger_is_capped() {
+    let mut bessie = Cow::new("Bessie");
+    let mut events = Vec::new();
+    for _ in 0..10 {
+        events.extend(bessie.tick());
+    }
+    assert_eq!(events, [EventKind::Hungry, EventKind::Starving]);
+    assert_eq!(bessie.hunger().level(), STARVING);
+
+    // 饿着的奶牛只在前三天产了奶。
+    assert_eq!(bessie.milk(), Some(EventKind::Milked { liters: 6 }));
+    assert_eq!(bessie.milk(), Some(EventKind::NoMilk));
+    assert_eq!(bessie.shear(), None);
+}
+
+#[test]
+fn farm_log_is_deterministic() {
+    let build = || {
+        let mut farm = Farm::new();
+        farm.add(Sheep::new("Dolly"));
+        farm.add(Cow::new("Bessie"));
+        farm.add(Chicken::new("Henny"));
+
+        farm.shear_all();
+        farm.run(2);
+        farm.milk("Bessie").unwrap();
+        farm.run(2);
+        farm.feed_hungry(6);
+        farm.shear("Dolly").unwrap();
+        farm.run(2);
+        farm
+    };
+
+    let farm = build();
+    assert_eq!(
+        lines(&farm),
+        [
+            "day 0: Dolly gets a haircut (3 wool)",
+            "day 2: Henny laid an egg",
+            "day 2: Bessie gave 4 liters of milk",
+            "day 3: Dolly has grown its wool back",
+            "day 4: Bessie is hungry",
+            "day 4: Henny laid an egg",
+            "day 4: Bessie was fed 6 (hunger 6)",
+            "day 4: Dolly gets a haircut (3 wool)",
+            "day 5: Dolly is hungry",
+            "day 6: Bessie is hungry",
+            "day 6: Henny laid an egg",
+        ]
+    );
+    assert_eq!(
+        farm.produce(),
+        Produce {
+            wool: 6,
+            milk: 4,
+            eggs: 3
+        }
+    );
+    assert_eq!(lines(&build()), lines(&farm));
+}
+
+#[test]
+fn heterogeneous_animals() {
+    let mut farm = Farm::new();
+    farm.add(Sheep::new("Dolly"));
+    farm.add_boxed(Box::new(Cow::new("Bessie")));
+    farm.add(Chicken::new("Henny"));
+
+    assert_eq!(
+        farm.chorus(),
+        [
+            "Dolly pauses briefly... baaaaah!",
+            "Bessie says moooooo!",
+            "Henny says cluck!"
+        ]
+    );
+    let kinds: Vec<&str> = farm.animals().map(|a| a.kind()).collect();
+    assert_eq!(kinds, ["sheep", "cow", "chicken"]);
+    assert_eq!(farm.find("Henny").map(|a| a.noise()), Some("cluck!"));
+
+    assert_eq!(
+        farm.shear("Bessie"),
+        Err(FarmError::Unsupported {
+            animal: "Bessie".to_owned(),
+            kind: "cow",
+            action: "shear",
+        })
+    );
+    assert_eq!(
+        farm.milk("Dolly").unwrap_err().to_string(),
+        "cannot milk Dolly, it is a sheep"
+    );
+    assert_eq!(
+        farm.feed("Shaun", 1),
+        Err(FarmError::NoSuchAnimal("Shaun".to_owned()))
+    );
+    // 失败的操作不进日志。
+    assert!(farm.log().is_empty());
+}
@@ -0,0 +1,437 @@
+/*!
+ * @Author: ZZX
+ * @Description: 标志位集合
+ * 扩展 `i_literals_and_operators::one`：那里只在 `u32` 字面量上打印
+ * `&`、`|`、`^`、`<<`、`>>` 的结果。`flag_set!` 声明一个带名字的标志位类型，
+ * 支持并、交、差、补运算，遍历已设置的标志，用 `A | B` 的形式显示，
+ * 支持 `{:b}` 格式化，并能从 `|` 分隔的字符串解析。
+ * @Date: create in 2026/10/19 5:46 上午
+ */
+
+use std::fmt::{self, Binary, Display, Formatter, LowerHex};
+use std::ops::{BitAnd, BitOr, BitXor, Not};
+
+/// 可以作为标志位底层存储的无符号整数。
+pub trait Bits:
+    Copy
+    + Eq
+    + Default
+    + Binary
+    + LowerHex
+    + BitAnd<Output = Self>
+    + BitOr<Output = Self>
+    + BitXor<Output = Self>
+    + Not<Output = Self>
+{
+}
+
+impl Bits for u8 {}
+impl Bits for u16 {}
+impl Bits for u32 {}
+impl Bits for u64 {}
+impl Bits for u128 {}
+
+/// 由 `flag_set!` 实现；遍历、显示和解析都建立在它之上。
+pub trait Flags: Copy + 'static {
+    type Bits: Bits;
+
+    /// 声明过的所有标志，按声明顺序排列。
+    const FLAGS: &'static [(&'static str, Self)];
+
+    fn bits(&self) -> Self::Bits;
+    /// 不检查未声明的位，原样保留。
+    fn from_bits_retain(bits: Self::Bits) -> Self;
+
+    fn empty() -> Self {
+        Self::from_bits_retain(Self::Bits::default())
+    }
+
+    /// 所有声明过的标志的并集。
+    fn all() -> Self {
+        let bits = Self::FLAGS
+            .iter()
+            .fold(Self::Bits::default(), |acc, (_, f)| acc | f.bits());
+        Self::from_bits_retain(bits)
+    }
+
+    /// 含有未声明的位时返回 `None`。
+    fn from_bits(bits: Self::Bits) -> Option<Self> {
+        if bits & !Self::all().bits() == Self::Bits::default() {
+            Some(Self::from_bits_retain(bits))
+        } else {
+            None
+        }
+    }
+
+    /// 丢掉未声明的位。
+    fn from_bits_truncate(bits: Self::Bits) -> Self {
+        Self::from_bits_retain(bits & Self::all().bits())
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::FLAGS
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|&(_, f)| f)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.bits() == Self::Bits::default()
+    }
+
+    fn is_all(&self) -> bool {
+        self.bits() == Self::all().bits()
+    }
+
+    fn contains(&self, other: Self) -> bool {
+        self.bits() & other.bits() == other.bits()
+    }
+
+    fn intersects(&self, other: Self) -> bool {
+        self.bits() & other.bits() != Self::Bits::default()
+    }
+
+    fn insert(&mut self, other: Self) {
+        *self = Self::from_bits_retain(self.bits() | other.bits());
+    }
+
+    fn remove(&mut self, other: Self) {
+        *self = Self::from_bits_retain(self.bits() & !other.bits());
+    }
+
+    fn toggle(&mut self, other: Self) {
+        *self = Self::from_bits_retain(self.bits() ^ other.bits());
+    }
+
+    /// 遍历集合中包含的每一个声明过的标志。
+    fn iter(&self) -> Iter<Self> {
+        Iter {
+            remaining: *self,
+            index: 0,
+        }
+    }
+}
+
+pub struct Iter<F> {
+    remaining: F,
+    index: usize,
+}
+
+impl<F: Flags> Iterator for Iter<F> {
+    type Item = (&'static str, F);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(&(name, flag)) = F::FLAGS.get(self.index) {
+            self.index += 1;
+            // 多位的组合标志只有在还没被前面的标志“用掉”时才输出。
+            if !flag.is_empty() && self.remaining.contains(flag) {
+                self.remaining.remove(flag);
+                return Some((name, flag));
+            }
+        }
+        None
+    }
+}
+
+/// 按 `A | B` 的形式显示；没有任何标志时显示 `(empty)`，未声明的位以十六进制附在最后。
+pub fn display<F: Flags>(flags: &F, f: &mut Formatter<'_>) -> fmt::Result {
+    let mut first = true;
+    let mut known = F::Bits::default();
+    for (name, flag) in flags.iter() {
+        if !first {
+            write!(f, " | ")?;
+        }
+        first = false;
+        known = known | flag.bits();
+        write!(f, "{}", name)?;
+    }
+
+    let unknown = flags.bits() & !known;
+    if unknown != F::Bits::default() {
+        if !first {
+            write!(f, " | ")?;
+        }
+        first = false;
+        write!(f, "{:#x}", unknown)?;
+    }
+
+    if first {
+        write!(f, "(empty)")?;
+    }
+    Ok(())
+}
+
+/// 解析标志位时遇到的未知名字。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseFlagError {
+    pub name: String,
+}
+
+impl Display for ParseFlagError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown flag `{}`", self.name)
+    }
+}
+
+/// 解析 `A | B` 形式的字符串，空白会被忽略，空串或 `(empty)` 得到空集合。
+pub fn parse<F: Flags>(s: &str) -> Result<F, ParseFlagError> {
+    let s = s.trim();
+    let mut flags = F::empty();
+    if s.is_empty() || s == "(empty)" {
+        return Ok(flags);
+    }
+
+    for part in s.split('|') {
+        let name = part.trim();
+        match F::from_name(name) {
+            Some(flag) => flags.insert(flag),
+            None => {
+                return Err(ParseFlagError {
+                    name: name.to_string(),
+                })
+            }
+        }
+    }
+    Ok(flags)
+}
+
+/// 声明一个标志位集合类型：
+///
+/// ```ignore
+/// flag_set! {
+///     pub struct Permissions: u8 {
+///         const READ = 0b001;
+///         const WRITE = 0b010;
+///     }
+/// }
+/// ```
+#[allow(unused_macros)]
+macro_rules! flag_set {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident: $bits:ty {
+            $($(#[$fmeta:meta])* const $flag:ident = $value:expr;)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+        $vis struct $name {
+            bits: $bits,
+        }
+
+        #[allow(dead_code)]
+        impl $name {
+            $($(#[$fmeta])* pub const $flag: $name = $name { bits: $value };)*
+        }
+
+        impl $crate::toolkit::flags::Flags for $name {
+            type Bits = $bits;
+
+            const FLAGS: &'static [(&'static str, Self)] = &[$((stringify!($flag), $name::$flag)),*];
+
+            fn bits(&self) -> $bits {
+                self.bits
+            }
+
+            fn from_bits_retain(bits: $bits) -> Self {
+                $name { bits }
+            }
+        }
+
+        impl std::ops::BitOr for $name {
+            type Output = Self;
+
+            fn bitor(self, rhs: Self) -> Self {
+                $name { bits: self.bits | rhs.bits }
+            }
+        }
+
+        impl std::ops::BitAnd for $name {
+            type Output = Self;
+
+            fn bitand(self, rhs: Self) -> Self {
+                $name { bits: self.bits & rhs.bits }
+            }
+        }
+
+        impl std::ops::BitXor for $name {
+            type Output = Self;
+
+            fn bitxor(self, rhs: Self) -> Self {
+                $name { bits: self.bits ^ rhs.bits }
+            }
+        }
+
+        // 差集：`a - b` 是在 `a` 中但不在 `b` 中的标志。
+        impl std::ops::Sub for $name {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                $name { bits: self.bits & !rhs.bits }
+            }
+        }
+
+        // 补集只在声明过的标志范围内取反。
+        impl std::ops::Not for $name {
+            type Output = Self;
+
+            fn not(self) -> Self {
+                use $crate::toolkit::flags::Flags;
+                Self::from_bits_truncate(!self.bits)
+            }
+        }
+
+        impl std::ops::BitOrAssign for $name {
+            fn bitor_assign(&mut self, rhs: Self) {
+                self.bits |= rhs.bits;
+            }
+        }
+
+        impl std::ops::BitAndAssign for $name {
+            fn bitand_assign(&mut self, rhs: Self) {
+                self.bits &= rhs.bits;
+            }
+        }
+
+        impl std::ops::BitXorAssign for $name {
+            fn bitxor_assign(&mut self, rhs: Self) {
+                self.bits ^= rhs.bits;
+            }
+        }
+
+        impl std::ops::SubAssign for $name {
+            fn sub_assign(&mut self, rhs: Self) {
+                self.bits &= !rhs.bits;
+            }
+        }
+
+        impl std::iter::FromIterator<$name> for $name {
+            fn from_iter<I: IntoIterator<Item = $name>>(iter: I) -> Self {
+                iter.into_iter().fold($name { bits: 0 }, |acc, f| acc | f)
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                $crate::toolkit::flags::display(self, f)
+            }
+        }
+
+        impl std::fmt::Debug for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}(", stringify!($name))?;
+                $crate::toolkit::flags::display(self, f)?;
+                write!(f, ")")
+            }
+        }
+
+        impl std::fmt::Binary for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                std::fmt::Binary::fmt(&self.bits, f)
+            }
+        }
+
+        impl std::str::FromStr for $name {
+            type Err = $crate::toolkit::flags::ParseFlagError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                $crate::toolkit::flags::parse(s)
+            }
+        }
+    };
+}
+
+#[allow(unused_imports)]
+pub(crate) use flag_set;
+
+#[cfg(test)]
+flag_set! {
+    struct Permissions: u8 {
+        const READ = 0b001;
+        const WRITE = 0b010;
+        const EXEC = 0b100;
+        /// 组合标志。
+        const READ_WRITE = 0b011;
+    }
+}
+
+#[test]
+fn set_operators() {
+    let rw = Permissions::READ | Permissions::WRITE;
+    assert_eq!(rw, Permissions::READ_WRITE);
+    assert_eq!(rw & Permissions::WRITE, Permissions::WRITE);
+    assert_eq!(rw - Permissions::READ, Permissions::WRITE);
+    assert_eq!(rw ^ Permissions::all(), Permissions::EXEC);
+    assert_eq!(!rw, Permissions::EXEC);
+    assert_eq!(!Permissions::empty(), Permissions::all());
+
+    let mut p = Permissions::empty();
+    p |= Permissions::EXEC;
+    p.insert(Permissions::READ);
+    assert!(p.contains(Permissions::READ | Permissions::EXEC));
+    assert!(!p.contains(Permissions::WRITE));
+    assert!(p.intersects(rw));
+    p.toggle(Permissions::all());
+    assert_eq!(p, Permissions::WRITE);
+    p -= Permissions::WRITE;
+    assert!(p.is_empty());
+    assert!(Permissions::all().is_all());
+}
+
+#[test]
+fn from_bits() {
+    assert_eq!(
+        Permissions::from_bits(0b101),
+        Some(Permissions::READ | Permissions::EXEC)
+    );
+    assert_eq!(Permissions::from_bits(0b1000), None);
+    assert_eq!(Permissions::from_bits_truncate(0b1111), Permissions::all());
+    assert_eq!(Permissions::from_bits_retain(0b1000).bits(), 0b1000);
+}
+
+#[test]
+fn iteration_and_display() {
+    let p = Permissions::READ | Permissions::EXEC;
+    let names: Vec<&str> = p.iter().map(|(name, _)| name).collect();
+    assert_eq!(names, vec!["READ", "EXEC"]);
+    assert_eq!(p.to_string(), "READ | EXEC");
+    assert_eq!(format!("{:?}", p), "Permissions(READ | EXEC)");
+
+    // 组合标志里的位不会被重复列出。
+    assert_eq!(Permissions::all().to_string(), "READ | WRITE | EXEC");
+    assert_eq!(Permissions::empty().to_string(), "(empty)");
+    assert_eq!(
+        Permissions::from_bits_retain(0b1001).to_string(),
+        "READ | 0x8"
+    );
+
+    assert_eq!(format!("{:b}", p), "101");
+    assert_eq!(format!("{:#06b}", p), "0b0101");
+
+    let collected: Permissions = vec![Permissions::READ, Permissions::WRITE]
+        .into_iter()
+        .collect();
+    assert_eq!(collected, Permissions::READ_WRITE);
+}
+
+#[test]
+fn parsing() {
+    assert_eq!(
+        "READ | EXEC".parse(),
+        Ok(Permissions::READ | Permissions::EXEC)
+    );
+    assert_eq!("WRITE|READ".parse(), Ok(Permissions::READ_WRITE));
+    assert_eq!("READ_WRITE".parse(), Ok(Permissions::READ_WRITE));
+    assert_eq!("".parse(), Ok(Permissions::empty()));
+    assert_eq!("(empty)".parse(), Ok(Permissions::empty()));
+
+    let err = "READ | DELETE".parse::<Permissions>().unwrap_err();
+    assert_eq!(err.to_string(), "unknown flag `DELETE`");
+    assert!("READ |".parse::<Permissions>().is_err());
+
+    // 显示结果可以原样解析回来。
+    for bits in 0..=0b111 {
+        let p = Permissions::from_bits(bits).unwrap();
+        assert_eq!(p.to_string().parse(), Ok(p));
+    }
+}
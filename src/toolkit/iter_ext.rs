@@ -0,0 +1,615 @@
+/*!
+ * @Author: ZZX
+ * @Description: 迭代器适配器
+ * 延续 `zh_hof::one` 的函数式写法：那里在 `0..` 上串起 `map`、`take_while`、
+ * `filter` 和 `fold`。`IterExt` 给所有迭代器补上分块、滑动窗口、交错、
+ * 按条件去重、带条件的累积、按键分组、笛卡尔积和有序合并。
+ * 它们都是惰性的，可以直接用在 `0..` 这样的无穷迭代器上。
+ * @Date: create in 2026/10/19 5:46 上午
+ */
+
+use std::collections::VecDeque;
+use std::iter::{Fuse, Peekable};
+
+/// `dedup` 和 `merge_sorted` 用的比较函数。
+pub type Compare<T> = fn(&T, &T) -> bool;
+
+pub trait IterExt: Iterator + Sized {
+    /// 每次取出 `size` 个元素，最后一块可能不满。
+    ///
+    /// # Panics
+    ///
+    /// `size` 为 0 时 panic。
+    fn chunks(self, size: usize) -> Chunks<Self> {
+        assert!(size > 0, "chunk size must be non-zero");
+        Chunks { iter: self, size }
+    }
+
+    /// 长度为 `size` 的滑动窗口；元素不足 `size` 个时什么也不产生。
+    ///
+    /// # Panics
+    ///
+    /// `size` 为 0 时 panic。
+    fn windows(self, size: usize) -> Windows<Self>
+    where
+        Self::Item: Clone,
+    {
+        assert!(size > 0, "window size must be non-zero");
+        Windows {
+            iter: self.fuse(),
+            size,
+            window: VecDeque::with_capacity(size),
+        }
+    }
+
+    /// 轮流从两个迭代器取元素，一个取完之后接着取另一个。
+    fn interleave<J>(self, other: J) -> Interleave<Self, J::IntoIter>
+    where
+        J: IntoIterator<Item = Self::Item>,
+    {
+        Interleave {
+            a: self.fuse(),
+            b: other.into_iter().fuse(),
+            next_is_a: true,
+        }
+    }
+
+    /// 去掉连续重复的元素，`same(前一个, 当前)` 为真时视为重复。
+    fn dedup_by<F>(self, same: F) -> DedupBy<Self, F>
+    where
+        F: FnMut(&Self::Item, &Self::Item) -> bool,
+    {
+        DedupBy {
+            iter: self.fuse(),
+            pending: None,
+            same,
+        }
+    }
+
+    fn dedup(self) -> DedupBy<Self, Compare<Self::Item>>
+    where
+        Self::Item: PartialEq,
+    {
+        self.dedup_by(PartialEq::eq)
+    }
+
+    /// 依次产生累积的状态，直到状态不再满足 `keep`。
+    /// 例如 `scan_while(0, |sum, n| sum + n, |&sum| sum < 100)` 产生小于 100 的前缀和。
+    fn scan_while<S, F, P>(self, init: S, step: F, keep: P) -> ScanWhile<Self, S, F, P>
+    where
+        S: Clone,
+        F: FnMut(&S, Self::Item) -> S,
+        P: FnMut(&S) -> bool,
+    {
+        ScanWhile {
+            iter: self,
+            state: Some(init),
+            step,
+            keep,
+        }
+    }
+
+    /// 把键相同的相邻元素放进同一组，产生 `(键, 组)`。
+    fn group_by_key<K, F>(self, key: F) -> GroupByKey<Self, K, F>
+    where
+        K: PartialEq,
+        F: FnMut(&Self::Item) -> K,
+    {
+        GroupByKey {
+            iter: self,
+            key,
+            pending: None,
+        }
+    }
+
+    /// 所有 `(a, b)` 组合，`a` 在外层；每换一个 `a` 就克隆一次 `other` 重新开始。
+    fn cartesian_product<J>(self, other: J) -> CartesianProduct<Self, J::IntoIter>
+    where
+        Self::Item: Clone,
+        J: IntoIterator,
+        J::IntoIter: Clone,
+    {
+        let other = other.into_iter();
+        CartesianProduct {
+            other_is_empty: other.clone().next().is_none(),
+            a: self,
+            current: None,
+            b: other.clone(),
+            b_orig: other,
+        }
+    }
+
+    /// 合并两个升序的迭代器，结果仍然升序；相等时先取 `self` 的元素。
+    fn merge_sorted<J>(self, other: J) -> MergeBy<Self, J::IntoIter, Compare<Self::Item>>
+    where
+        J: IntoIterator<Item = Self::Item>,
+        Self::Item: Ord,
+    {
+        self.merge_by(other, |a, b| a <= b)
+    }
+
+    /// 按 `first(a, b)` 合并：为真时先取左边的 `a`。
+    fn merge_by<J, F>(self, other: J, first: F) -> MergeBy<Self, J::IntoIter, F>
+    where
+        J: IntoIterator<Item = Self::Item>,
+        F: FnMut(&Self::Item, &Self::Item) -> bool,
+    {
+        MergeBy {
+            a: self.peekable(),
+            b: other.into_iter().peekable(),
+            first,
+        }
+    }
+}
+
+impl<I: Iterator> IterExt for I {}
+
+pub struct Chunks<I> {
+    iter: I,
+    size: usize,
+}
+
+impl<I: Iterator> Iterator for Chunks<I> {
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Vec<I::Item>> {
+        let chunk: Vec<I::Item> = self.iter.by_ref().take(self.size).collect();
+        if chunk.is_empty() {
+            None
+        } else {
+            Some(chunk)
+        }
+    }
+}
+
+pub struct Windows<I: Iterator> {
+    // 源迭代器返回 `None` 之后不再调用它。
+    iter: Fuse<I>,
+    size: usize,
+    window: VecDeque<I::Item>,
+}
+
+impl<I> Iterator for Windows<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Vec<I::Item>> {
+        if self.window.len() == self.size {
+            self.window.pop_front();
+        }
+        while self.window.len() < self.size {
+            self.window.push_back(self.iter.next()?);
+        }
+        Some(self.window.iter().cloned().collect())
+    }
+}
+
+pub struct Interleave<I, J> {
+    a: Fuse<I>,
+    b: Fuse<J>,
+    next_is_a: bool,
+}
+
+impl<I, J> Iterator for Interleave<I, J>
+where
+    I: Iterator,
+    J: Iterator<Item = I::Item>,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let item = if self.next_is_a {
+            self.a.next().or_else(|| self.b.next())
+        } else {
+            self.b.next().or_else(|| self.a.next())
+        };
+        self.next_is_a = !self.next_is_a;
+        item
+    }
+}
+
+pub struct DedupBy<I: Iterator, F> {
+    iter: Fuse<I>,
+    pending: Option<I::Item>,
+    same: F,
+}
+
+impl<I, F> Iterator for DedupBy<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item, &I::Item) -> bool,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let current = match self.pending.take() {
+            Some(item) => item,
+            None => self.iter.next()?,
+        };
+        for item in self.iter.by_ref() {
+            if !(self.same)(&current, &item) {
+                self.pending = Some(item);
+                break;
+            }
+        }
+        Some(current)
+    }
+}
+
+pub struct ScanWhile<I, S, F, P> {
+    iter: I,
+    /// 为 `None` 表示已经结束。
+    state: Option<S>,
+    step: F,
+    keep: P,
+}
+
+impl<I, S, F, P> Iterator for ScanWhile<I, S, F, P>
+where
+    I: Iterator,
+    S: Clone,
+    F: FnMut(&S, I::Item) -> S,
+    P: FnMut(&S) -> bool,
+{
+    type Item = S;
+
+    fn next(&mut self) -> Option<S> {
+        let state = self.state.as_ref()?;
+        let next = match self.iter.next() {
+            Some(item) => (self.step)(state, item),
+            None => {
+                self.state = None;
+                return None;
+            }
+        };
+        if (self.keep)(&next) {
+            self.state = Some(next.clone());
+            Some(next)
+        } else {
+            self.state = None;
+            None
+        }
+    }
+}
+
+pub struct GroupByKey<I: Iterator, K, F> {
+    iter: I,
+    key: F,
+    /// 上一组多读出来的第一个元素及其键。
+    pending: Option<(K, I::Item)>,
+}
+
+impl<I, K, F> Iterator for GroupByKey<I, K, F>
+where
+    I: Iterator,
+    K: PartialEq,
+    F: FnMut(&I::Item) -> K,
+{
+    type Item = (K, Vec<I::Item>);
+
+    fn next(&mut self) -> Option<(K, Vec<I::Item>)> {
+        let (key, first) = match self.pending.take() {
+            Some(pending) => pending,
+            None => {
+                let item = self.iter.next()?;
+                ((self.key)(&item), item)
+            }
+        };
+
+        let mut group = vec![first];
+        for item in self.iter.by_ref() {
+            let k = (self.key)(&item);
+            if k == key {
+                group.push(item);
+            } else {
+                self.pending = Some((k, item));
+                break;
+            }
+        }
+        Some((key, group))
+    }
+}
+
+pub struct CartesianProduct<I: Iterator, J> {
+    a: I,
+    current: Option<I::Item>,
+    b: J,
+    b_orig: J,
+    /// `other` 为空时积也为空，不必把 `self` 取完；`self` 是无穷迭代器时也能结束。
+    other_is_empty: bool,
+}
+
+impl<I, J> Iterator for CartesianProduct<I, J>
+where
+    I: Iterator,
+    I::Item: Clone,
+    J: Iterator + Clone,
+{
+    type Item = (I::Item, J::Item);
+
+    fn next(&mut self) -> Option<(I::Item, J::Item)> {
+        if self.other_is_empty {
+            return None;
+        }
+        loop {
+            if self.current.is_none() {
+                self.current = Some(self.a.next()?);
+                self.b = self.b_orig.clone();
+            }
+            match self.b.next() {
+                Some(b) => return self.current.clone().map(|a| (a, b)),
+                None => self.current = None,
+            }
+        }
+    }
+}
+
+pub struct MergeBy<I: Iterator, J: Iterator, F> {
+    a: Peekable<I>,
+    b: Peekable<J>,
+    first: F,
+}
+
+impl<I, J, F> Iterator for MergeBy<I, J, F>
+where
+    I: Iterator,
+    J: Iterator<Item = I::Item>,
+    F: FnMut(&I::Item, &I::Item) -> bool,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let take_a = match (self.a.peek(), self.b.peek()) {
+            (Some(a), Some(b)) => (self.first)(a, b),
+            (Some(_), None) => true,
+            (None, _) => false,
+        };
+        if take_a {
+            self.a.next()
+        } else {
+            self.b.next()
+        }
+    }
+}
+
+// 下面是用切片和循环直接写出的参照实现，测试里拿来和惰性版本比较。
+#[cfg(test)]
+mod reference {
+    pub fn interleave(a: &[i32], b: &[i32]) -> Vec<i32> {
+        let mut out = Vec::new();
+        for i in 0..a.len().max(b.len()) {
+            out.extend(a.get(i));
+            out.extend(b.get(i));
+        }
+        out
+    }
+
+    pub fn dedup_by_key(v: &[i32], key: fn(i32) -> i32) -> Vec<i32> {
+        let mut out = v.to_vec();
+        out.dedup_by_key(|x| key(*x));
+        out
+    }
+
+    pub fn group_by_key(v: &[i32], key: fn(i32) -> i32) -> Vec<(i32, Vec<i32>)> {
+        let mut out: Vec<(i32, Vec<i32>)> = Vec::new();
+        for &x in v {
+            match out.last_mut() {
+                Some((k, group)) if *k == key(x) => group.push(x),
+                _ => out.push((key(x), vec![x])),
+            }
+        }
+        out
+    }
+
+    pub fn prefix_sums_below(v: &[i32], limit: i32) -> Vec<i32> {
+        let mut out = Vec::new();
+        let mut sum = 0;
+        for &x in v {
+            sum += x;
+            if sum >= limit {
+                break;
+            }
+            out.push(sum);
+        }
+        out
+    }
+
+    pub fn cartesian(a: &[i32], b: &[char]) -> Vec<(i32, char)> {
+        let mut out = Vec::new();
+        for &x in a {
+            for &y in b {
+                out.push((x, y));
+            }
+        }
+        out
+    }
+
+    pub fn merge(a: &[i32], b: &[i32]) -> Vec<i32> {
+        let mut out = [a, b].concat();
+        // 稳定排序：相等时 `a` 的元素在前。
+        out.sort();
+        out
+    }
+
+    /// 一组测试输入，包括空的、单个元素的和带重复的。
+    pub fn inputs() -> Vec<Vec<i32>> {
+        vec![
+            vec![],
+            vec![5],
+            vec![1, 1, 2, 3, 3, 3, 4, 1, 1],
+            (0..20).collect(),
+            vec![7, -2, 7, 7, 0, 13, 13, -5, 8, 8, 8, 2],
+        ]
+    }
+}
+
+#[test]
+fn chunks_and_windows_match_slices() {
+    for v in reference::inputs() {
+        for n in 1..6 {
+            let chunks: Vec<Vec<i32>> = v.iter().copied().chunks(n).collect();
+            let expected: Vec<Vec<i32>> = v.chunks(n).map(<[i32]>::to_vec).collect();
+            assert_eq!(chunks, expected, "chunks({}) of {:?}", n, v);
+
+            let windows: Vec<Vec<i32>> = v.iter().copied().windows(n).collect();
+            let expected: Vec<Vec<i32>> = v.windows(n).map(<[i32]>::to_vec).collect();
+            assert_eq!(windows, expected, "windows({}) of {:?}", n, v);
+        }
+    }
+}
+
+#[test]
+fn interleave_dedup_and_groups() {
+    let inputs = reference::inputs();
+    for a in &inputs {
+        for b in &inputs {
+            let lazy: Vec<i32> = a.iter().copied().interleave(b.iter().copied()).collect();
+            assert_eq!(lazy, reference::interleave(a, b));
+        }
+
+        let parity = |x: i32| x.rem_euclid(2);
+        let identity = |x: i32| x;
+        for key in [parity as fn(i32) -> i32, identity] {
+            let deduped: Vec<i32> = a
+                .iter()
+                .copied()
+                .dedup_by(|p, c| key(*p) == key(*c))
+                .collect();
+            assert_eq!(deduped, reference::dedup_by_key(a, key));
+
+            let groups: Vec<(i32, Vec<i32>)> =
+                a.iter().copied().group_by_key(|x| key(*x)).collect();
+            assert_eq!(groups, reference::group_by_key(a, key));
+        }
+        assert_eq!(
+            a.iter().copied().dedup().collect::<Vec<_>>(),
+            reference::dedup_by_key(a, identity)
+        );
+    }
+}
+
+#[test]
+fn scan_products_and_merges() {
+    let inputs = reference::inputs();
+    for a in &inputs {
+        for limit in [0, 1, 10, 100] {
+            let sums: Vec<i32> = a
+                .iter()
+                .copied()
+                .scan_while(0, |s, x| s + x, |&s| s < limit)
+                .collect();
+            assert_eq!(sums, reference::prefix_sums_below(a, limit));
+        }
+
+        let letters = ['x', 'y', 'z'];
+        let product: Vec<(i32, char)> = a
+            .iter()
+            .copied()
+            .cartesian_product(letters.iter().copied())
+            .collect();
+        assert_eq!(product, reference::cartesian(a, &letters));
+
+        for b in &inputs {
+            let (mut a, mut b) = (a.clone(), b.clone());
+            a.sort();
+            b.sort();
+            let merged: Vec<i32> = a.iter().copied().merge_sorted(b.iter().copied()).collect();
+            assert_eq!(merged, reference::merge(&a, &b));
+        }
+    }
+
+    // 相等时先取左边：用键比较，带上来源标记。
+    let left = [(1, 'l'), (2, 'l')];
+    let right = [(1, 'r'), (3, 'r')];
+    let merged: Vec<(i32, char)> = left
+        .iter()
+        .copied()
+        .merge_by(right.iter().copied(), |a, b| a.0 <= b.0)
+        .collect();
+    assert_eq!(merged, vec![(1, 'l'), (1, 'r'), (2, 'l'), (3, 'r')]);
+}
+
+#[test]
+fn adaptors_are_lazy() {
+    use std::cell::Cell;
+
+    // 都能接在无穷迭代器后面。
+    assert_eq!((0..).chunks(3).nth(2), Some(vec![6, 7, 8]));
+    assert_eq!((0..).windows(2).nth(5), Some(vec![5, 6]));
+    assert_eq!(
+        (0..)
+            .step_by(2)
+            .interleave((1..).step_by(2))
+            .take(5)
+            .collect::<Vec<_>>(),
+        vec![0, 1, 2, 3, 4]
+    );
+    assert_eq!((0..).map(|n| n / 3).dedup().nth(4), Some(4));
+    assert_eq!(
+        (0..)
+            .group_by_key(|n| n / 10)
+            .nth(2)
+            .map(|(k, g)| (k, g.len())),
+        Some((2, 10))
+    );
+    assert_eq!((0..).cartesian_product(0..2).nth(5), Some((2, 1)));
+    assert_eq!(
+        (0..)
+            .map(|n| n * 3)
+            .merge_sorted((0..).map(|n| n * 5))
+            .take(6)
+            .collect::<Vec<_>>(),
+        vec![0, 0, 3, 5, 6, 9]
+    );
+    assert_eq!((1..).scan_while(0, |s, n| s + n, |&s| s < 10).count(), 3);
+
+    // 只在需要时才从源迭代器取元素。
+    let pulled = Cell::new(0);
+    let source = (0..100).inspect(|_| pulled.set(pulled.get() + 1));
+    let first = source.chunks(4).next();
+    assert_eq!(first, Some(vec![0, 1, 2, 3]));
+    assert_eq!(pulled.get(), 4);
+}
+
+#[test]
+fn exhausted_sources_are_left_alone() {
+    use std::cell::Cell;
+
+    // 两个元素之后返回 `None`，记下一共被调用了几次。
+    let calls = Cell::new(0);
+    let source = || {
+        std::iter::from_fn(|| {
+            calls.set(calls.get() + 1);
+            if calls.get() <= 2 {
+                Some(calls.get())
+            } else {
+                None
+            }
+        })
+    };
+    let mut windows = source().windows(3);
+    assert_eq!(windows.next(), None);
+    assert_eq!(windows.next(), None);
+    assert_eq!(calls.get(), 3);
+
+    calls.set(0);
+    let mut dedup = source().dedup_by(|a, b| a == b);
+    assert_eq!(dedup.next(), Some(1));
+    assert_eq!(dedup.next(), Some(2));
+    assert_eq!(dedup.next(), None);
+    assert_eq!(dedup.next(), None);
+    assert_eq!(calls.get(), 3);
+
+    // 空的 `other` 不会让无穷的 `self` 一直转下去。
+    assert_eq!((0..).cartesian_product(0..0).next(), None);
+    assert_eq!((0..3).cartesian_product(Vec::<u8>::new()).count(), 0);
+}
+
+#[test]
+#[should_panic(expected = "window size must be non-zero")]
+fn zero_sized_windows_panic() {
+    let _ = (0..3).windows(0);
+}
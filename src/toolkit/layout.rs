@@ -0,0 +1,368 @@
+/*!
+ * @Author: ZZX
+ * @Description: 内存布局
+ * 扩展 `k_array_and_slice::one` 和 `r_casting::two` 里的 `mem::size_of_val`：
+ * 对章节中出现过的类型列出大小、对齐、字段偏移、填充字节，
+ * 以及枚举和 `Option` 是否利用了“空位”（niche）来省掉标签。
+ * 通过 `cargo run -- layout` 打印报告。
+ * @Date: create in 2026/10/19 5:46 上午
+ */
+
+use std::fmt::{self, Display, Formatter};
+use std::mem;
+
+use crate::example::j_tuple::Matrix;
+use crate::example::l_struct::{Pair, Person, Point, Rectangle};
+use crate::example::m_enum::WebEvent;
+use crate::example::p_linked_list::List;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldLayout {
+    pub name: &'static str,
+    pub offset: usize,
+    pub size: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariantLayout {
+    pub name: &'static str,
+    /// 把这个变体的所有字段放进一个元组时的大小。
+    pub payload_size: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Shape {
+    Struct(Vec<FieldLayout>),
+    Enum(Vec<VariantLayout>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeLayout {
+    pub name: &'static str,
+    pub size: usize,
+    pub align: usize,
+    /// `Option<T>` 的大小。
+    pub option_size: usize,
+    pub shape: Shape,
+}
+
+impl TypeLayout {
+    /// 结构体中不属于任何字段的字节数；枚举中除了最大负载之外的字节数（标签加填充）。
+    pub fn padding(&self) -> usize {
+        match &self.shape {
+            Shape::Struct(fields) => self.size - fields.iter().map(|f| f.size).sum::<usize>(),
+            Shape::Enum(_) => self.size - self.max_payload(),
+        }
+    }
+
+    /// 结构体中填充字节所在的区间 `(offset, len)`。
+    pub fn padding_ranges(&self) -> Vec<(usize, usize)> {
+        let fields = match &self.shape {
+            Shape::Struct(fields) => fields,
+            Shape::Enum(_) => return Vec::new(),
+        };
+
+        let mut sorted: Vec<&FieldLayout> = fields.iter().collect();
+        sorted.sort_by_key(|f| f.offset);
+
+        let mut ranges = Vec::new();
+        let mut cursor = 0;
+        for field in sorted {
+            if field.offset > cursor {
+                ranges.push((cursor, field.offset - cursor));
+            }
+            cursor = cursor.max(field.offset + field.size);
+        }
+        if self.size > cursor {
+            ranges.push((cursor, self.size - cursor));
+        }
+        ranges
+    }
+
+    fn max_payload(&self) -> usize {
+        match &self.shape {
+            Shape::Enum(variants) => variants.iter().map(|v| v.payload_size).max().unwrap_or(0),
+            Shape::Struct(_) => self.size,
+        }
+    }
+
+    /// 枚举的标签是否藏进了某个字段的空位，不占额外空间。
+    ///
+    /// 这是根据大小推断的：枚举和最大的负载一样大，就认为标签没有占地方。
+    /// 编译器不公开标签实际放在哪里，负载末尾的填充字节也可能被算成空位。
+    pub fn enum_uses_niche(&self) -> bool {
+        match &self.shape {
+            Shape::Enum(variants) => variants.len() > 1 && self.size == self.max_payload(),
+            Shape::Struct(_) => false,
+        }
+    }
+
+    /// `Option<T>` 是否和 `T` 一样大。
+    pub fn option_uses_niche(&self) -> bool {
+        self.option_size == self.size
+    }
+}
+
+impl Display for TypeLayout {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{}: size {}, align {}, padding {}",
+            self.name,
+            self.size,
+            self.align,
+            self.padding()
+        )?;
+
+        match &self.shape {
+            Shape::Struct(fields) => {
+                for field in fields {
+                    writeln!(
+                        f,
+                        "    .{:<8} offset {:>3}, size {:>3}",
+                        field.name, field.offset, field.size
+                    )?;
+                }
+                for (offset, len) in self.padding_ranges() {
+                    writeln!(f, "    (pad)     offset {:>3}, size {:>3}", offset, len)?;
+                }
+            }
+            Shape::Enum(variants) => {
+                for variant in variants {
+                    writeln!(
+                        f,
+                        "    {:<10} payload {:>3}",
+                        variant.name, variant.payload_size
+                    )?;
+                }
+                writeln!(
+                    f,
+                    "    tag: {}",
+                    if self.enum_uses_niche() {
+                        "stored in a niche"
+                    } else {
+                        "separate"
+                    }
+                )?;
+            }
+        }
+
+        write!(
+            f,
+            "    Option<{}>: size {}{}",
+            self.name,
+            self.option_size,
+            if self.option_uses_niche() {
+                " (niche)"
+            } else {
+                ""
+            }
+        )
+    }
+}
+
+/// 返回字段类型的大小；字段由闭包 `|v| &v.field` 指出。
+pub fn field_size<T, F>(_: for<'a> fn(&'a T) -> &'a F) -> usize {
+    mem::size_of::<F>()
+}
+
+/// 描述一个结构体：`struct_layout!("Point" => Point { x, y })`。
+/// 名字是 `layout` 子命令里用的名字，不用带上生命周期之类的参数。
+macro_rules! struct_layout {
+    ($name:literal => $t:ty { $($field:tt),* $(,)? }) => {
+        TypeLayout {
+            name: $name,
+            size: mem::size_of::<$t>(),
+            align: mem::align_of::<$t>(),
+            option_size: mem::size_of::<Option<$t>>(),
+            shape: Shape::Struct(vec![$(
+                FieldLayout {
+                    name: stringify!($field),
+                    offset: mem::offset_of!($t, $field),
+                    size: field_size::<$t, _>(|v| &v.$field),
+                },
+            )*]),
+        }
+    };
+}
+
+/// 描述一个枚举，变体的写法和定义里一样，只是去掉了可见性和属性：
+/// `enum_layout!("List" => List { Cons(u32, Box<List>), Nil })`。
+///
+/// 编译测试时，列出的变体会和真正的枚举对照：`match` 保证一个不少，
+/// 把变体转换成 `fn(字段类型...) -> 枚举` 保证字段类型一致。
+macro_rules! enum_layout {
+    ($name:literal => $t:ident {
+        $($variant:ident $(($($field:ty),*))? $({$($named:ident: $named_ty:ty),*})?),* $(,)?
+    }) => {{
+        // 只在编译测试时检查，免得普通构建把章节里从没用过的变体算成“构造过”。
+        #[cfg(test)]
+        fn check(value: &$t) {
+            match value {
+                $(enum_layout!(
+                    @pattern $t $variant $(($($field),*))? $({$($named: $named_ty),*})?
+                ) => {})*
+            }
+            $(enum_layout!(@check $t $variant $(($($field),*))? $({$($named: $named_ty),*})?);)*
+        }
+
+        TypeLayout {
+            name: $name,
+            size: mem::size_of::<$t>(),
+            align: mem::align_of::<$t>(),
+            option_size: mem::size_of::<Option<$t>>(),
+            shape: Shape::Enum(vec![$(
+                VariantLayout {
+                    name: stringify!($variant),
+                    payload_size: mem::size_of::<(
+                        $($($field,)*)? $($($named_ty,)*)?
+                    )>(),
+                },
+            )*]),
+        }
+    }};
+
+    (@pattern $t:ident $variant:ident) => { $t::$variant };
+    (@pattern $t:ident $variant:ident ($($field:ty),*)) => { $t::$variant(..) };
+    (@pattern $t:ident $variant:ident {$($named:ident: $named_ty:ty),*}) => { $t::$variant { .. } };
+
+    (@check $t:ident $variant:ident) => {
+        let _: $t = $t::$variant;
+    };
+    (@check $t:ident $variant:ident ($($field:ty),*)) => {
+        let _: fn($($field),*) -> $t = $t::$variant;
+    };
+    (@check $t:ident $variant:ident {$($named:ident: $named_ty:ty),*}) => {
+        let _: fn($($named_ty),*) -> $t = |$($named),*| $t::$variant { $($named),* };
+    };
+}
+
+/// 所有登记过的章节类型。
+pub fn registry() -> Vec<TypeLayout> {
+    vec![
+        struct_layout!("Point" => Point { x, y }),
+        struct_layout!("Rectangle" => Rectangle { p1, p2 }),
+        struct_layout!("Pair" => Pair { 0, 1 }),
+        struct_layout!("Person" => Person<'static> { name, age }),
+        struct_layout!("Matrix" => Matrix { 0, 1, 2, 3 }),
+        enum_layout!("WebEvent" => WebEvent {
+            PageLoad,
+            PageUnload,
+            KeyPress(char),
+            Paste(String),
+            Click { x: i64, y: i64 },
+        }),
+        enum_layout!("List" => List {
+            Cons(u32, Box<List>),
+            Nil,
+        }),
+    ]
+}
+
+pub fn find(name: &str) -> Option<TypeLayout> {
+    registry().into_iter().find(|layout| layout.name == name)
+}
+
+/// `layout` 子命令：不带参数时打印全部类型，否则只打印指定的类型。
+pub fn run(names: &[String]) -> Result<(), String> {
+    if names.is_empty() {
+        for layout in registry() {
+            println!("{}", layout);
+        }
+        return Ok(());
+    }
+
+    for name in names {
+        match find(name) {
+            Some(layout) => println!("{}", layout),
+            None => return Err(format!("unknown type `{}`", name)),
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn struct_layouts() {
+    let point = find("Point").unwrap();
+    assert_eq!((point.size, point.align, point.padding()), (8, 4, 0));
+    assert_eq!(
+        point.shape,
+        Shape::Struct(vec![
+            FieldLayout {
+                name: "x",
+                offset: 0,
+                size: 4
+            },
+            FieldLayout {
+                name: "y",
+                offset: 4,
+                size: 4
+            },
+        ])
+    );
+    // `f32` 没有空位，`Option<Point>` 需要额外的标签。
+    assert!(!point.option_uses_niche());
+    assert_eq!(point.option_size, 12);
+
+    let rectangle = find("Rectangle").unwrap();
+    assert_eq!((rectangle.size, rectangle.align), (16, 4));
+    assert_eq!(rectangle.padding_ranges(), vec![]);
+
+    let matrix = find("Matrix").unwrap();
+    assert_eq!((matrix.size, matrix.padding()), (16, 0));
+
+    let pair = find("Pair").unwrap();
+    assert_eq!((pair.size, pair.align), (8, 4));
+}
+
+#[test]
+fn padding_and_reference_niche() {
+    let word = mem::size_of::<usize>();
+    let person = find("Person").unwrap();
+
+    // `&str` 占两个字，`u8` 后面补齐到一个字。
+    assert_eq!(person.size, 3 * word);
+    assert_eq!(person.align, word);
+    assert_eq!(person.padding(), word - 1);
+    assert_eq!(person.padding_ranges().len(), 1);
+    assert_eq!(person.padding_ranges()[0].1, word - 1);
+    // 引用不能为空，`Option<Person>` 不需要额外空间。
+    assert!(person.option_uses_niche());
+}
+
+#[test]
+fn enum_layouts() {
+    let word = mem::size_of::<usize>();
+
+    // `Nil` 用的是 `Box` 的空指针这个空位。
+    let list = find("List").unwrap();
+    assert_eq!(list.size, 2 * word);
+    assert!(list.enum_uses_niche());
+    assert_eq!(list.padding(), 0);
+
+    let event = find("WebEvent").unwrap();
+    assert_eq!(event.size, mem::size_of::<WebEvent>());
+    assert_eq!(event.align, mem::align_of::<WebEvent>());
+    if cfg!(target_pointer_width = "64") {
+        // `String` 的容量不会超过 `isize::MAX`，标签和 `Option` 的标签都放在那里。
+        assert_eq!((event.size, event.align, event.padding()), (24, 8, 0));
+        assert!(event.enum_uses_niche());
+    }
+    assert_eq!(event.option_size, event.size);
+    assert!(event.option_uses_niche());
+}
+
+#[test]
+fn report_format() {
+    let text = find("Point").unwrap().to_string();
+    assert_eq!(
+        text,
+        "Point: size 8, align 4, padding 0\n\
+         \x20   .x        offset   0, size   4\n\
+         \x20   .y        offset   4, size   4\n\
+         \x20   Option<Point>: size 12"
+    );
+
+    assert!(run(&["Nope".to_string()]).is_err());
+    assert!(run(&["List".to_string()]).is_ok());
+}
@@ -0,0 +1,316 @@
+/*!
+ * @Author: ZZX
+ * @Description: 惰性求值
+ * 扩展 `zf_closure_type_anonymity::third`：`create_fn_once` 返回的 `impl FnOnce()`
+ * 只能调用一次，调用者得自己记住有没有调用过。`Lazy` 把一个 `FnOnce() -> T` 存起来，
+ * 第一次访问时才调用，之后一直返回缓存的结果；初始化时 panic 会让它中毒，
+ * 以后的访问都报错而不是再调用一次。`SyncLazy` 是线程安全的版本，
+ * 多个线程同时访问时也只有一个线程执行初始化，其余的等它完成。
+ * @Date: create in 2026/10/19 5:46 上午
+ */
+
+use std::cell::{Cell, OnceCell};
+use std::fmt::{self, Display, Formatter};
+use std::mem;
+use std::ops::Deref;
+use std::sync::{Mutex, OnceLock};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LazyError {
+    /// 初始化时 panic 了。
+    Poisoned,
+    /// 初始化函数里又访问了自己。
+    Reentrant,
+}
+
+impl Display for LazyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            LazyError::Poisoned => write!(
+                f,
+                "lazy value was poisoned by a panic during initialization"
+            ),
+            LazyError::Reentrant => {
+                write!(f, "lazy value was accessed during its own initialization")
+            }
+        }
+    }
+}
+
+enum State<F> {
+    Pending(F),
+    Running,
+    Poisoned,
+    Done,
+}
+
+/// 单线程的惰性值。
+pub struct Lazy<T, F = fn() -> T> {
+    value: OnceCell<T>,
+    state: Cell<State<F>>,
+}
+
+/// 把初始化函数装箱，不同的闭包也能放进同一个类型里。
+pub type Thunk<'a, T> = Lazy<T, Box<dyn FnOnce() -> T + 'a>>;
+
+/// 初始化函数 panic 时在栈展开的过程中把状态改成中毒。
+struct PoisonOnUnwind<'a, F> {
+    state: &'a Cell<State<F>>,
+}
+
+impl<F> Drop for PoisonOnUnwind<'_, F> {
+    fn drop(&mut self) {
+        self.state.set(State::Poisoned);
+    }
+}
+
+impl<T, F> Lazy<T, F> {
+    pub const fn new(init: F) -> Self {
+        Lazy {
+            value: OnceCell::new(),
+            state: Cell::new(State::Pending(init)),
+        }
+    }
+
+    /// 已经初始化过时返回值，不会触发初始化。
+    pub fn get(&self) -> Option<&T> {
+        self.value.get()
+    }
+
+    pub fn is_poisoned(&self) -> bool {
+        let state = self.state.replace(State::Poisoned);
+        let poisoned = matches!(state, State::Poisoned);
+        self.state.set(state);
+        poisoned
+    }
+}
+
+impl<'a, T> Lazy<T, Box<dyn FnOnce() -> T + 'a>> {
+    pub fn boxed<F: FnOnce() -> T + 'a>(init: F) -> Self {
+        Lazy::new(Box::new(init))
+    }
+}
+
+impl<T, F: FnOnce() -> T> Lazy<T, F> {
+    pub fn try_force(&self) -> Result<&T, LazyError> {
+        if let Some(value) = self.value.get() {
+            return Ok(value);
+        }
+
+        match self.state.replace(State::Running) {
+            State::Pending(init) => {
+                let guard = PoisonOnUnwind { state: &self.state };
+                let value = init();
+                mem::forget(guard);
+                self.state.set(State::Done);
+                Ok(self.value.get_or_init(|| value))
+            }
+            State::Running => Err(LazyError::Reentrant),
+            State::Poisoned => {
+                self.state.set(State::Poisoned);
+                Err(LazyError::Poisoned)
+            }
+            State::Done => unreachable!("value is set before the state becomes done"),
+        }
+    }
+
+    /// # Panics
+    ///
+    /// 中毒或者在初始化函数里访问自己时 panic。
+    pub fn force(&self) -> &T {
+        self.try_force().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn into_inner(self) -> Result<T, LazyError> {
+        self.try_force()?;
+        Ok(self.value.into_inner().expect("forced above"))
+    }
+}
+
+impl<T, F: FnOnce() -> T> Deref for Lazy<T, F> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.force()
+    }
+}
+
+impl<T: fmt::Debug, F> fmt::Debug for Lazy<T, F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.get() {
+            Some(value) => f.debug_tuple("Lazy").field(value).finish(),
+            None if self.is_poisoned() => write!(f, "Lazy(<poisoned>)"),
+            None => write!(f, "Lazy(<uninit>)"),
+        }
+    }
+}
+
+/// 线程安全的惰性值，可以放在 `static` 里。
+pub struct SyncLazy<T, F = fn() -> T> {
+    value: OnceLock<T>,
+    /// 初始化期间一直锁着；初始化函数 panic 时锁也跟着中毒。
+    init: Mutex<Option<F>>,
+}
+
+impl<T, F> SyncLazy<T, F> {
+    pub const fn new(init: F) -> Self {
+        SyncLazy {
+            value: OnceLock::new(),
+            init: Mutex::new(Some(init)),
+        }
+    }
+
+    pub fn get(&self) -> Option<&T> {
+        self.value.get()
+    }
+
+    pub fn is_poisoned(&self) -> bool {
+        self.init.is_poisoned()
+    }
+}
+
+impl<T, F: FnOnce() -> T> SyncLazy<T, F> {
+    pub fn try_force(&self) -> Result<&T, LazyError> {
+        if let Some(value) = self.value.get() {
+            return Ok(value);
+        }
+
+        let mut init = self.init.lock().map_err(|_| LazyError::Poisoned)?;
+        // 等锁的时候可能已经被别的线程初始化好了。
+        if let Some(value) = self.value.get() {
+            return Ok(value);
+        }
+        let init = init.take().expect("initializer is taken only once");
+        Ok(self.value.get_or_init(init))
+    }
+
+    /// # Panics
+    ///
+    /// 中毒时 panic。在初始化函数里访问自己会死锁。
+    pub fn force(&self) -> &T {
+        self.try_force().unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl<T, F: FnOnce() -> T> Deref for SyncLazy<T, F> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.force()
+    }
+}
+
+#[test]
+fn evaluates_once() {
+    let calls = Cell::new(0);
+    let lazy = Lazy::new(|| {
+        calls.set(calls.get() + 1);
+        "FnOnce".to_owned()
+    });
+
+    assert_eq!(lazy.get(), None);
+    assert_eq!(format!("{:?}", lazy), "Lazy(<uninit>)");
+    assert_eq!(calls.get(), 0);
+
+    assert_eq!(lazy.force(), "FnOnce");
+    assert_eq!(lazy.len(), 6);
+    assert_eq!(*lazy, "FnOnce");
+    assert_eq!(lazy.get().map(String::as_str), Some("FnOnce"));
+    assert_eq!(format!("{:?}", lazy), "Lazy(\"FnOnce\")");
+    assert_eq!(calls.get(), 1);
+
+    assert_eq!(lazy.into_inner(), Ok("FnOnce".to_owned()));
+    assert_eq!(calls.get(), 1);
+
+    // 从来没访问过就丢掉，初始化函数不会被调用。
+    {
+        let _unused: Lazy<i32, _> = Lazy::new(|| {
+            calls.set(calls.get() + 1);
+            1
+        });
+    }
+    assert_eq!(calls.get(), 1);
+}
+
+#[test]
+fn thunks_hold_different_closures() {
+    fn create_fn_once(text: &str) -> impl FnOnce() -> String + '_ {
+        move || format!("This is a: {}", text)
+    }
+
+    let suffix = "!".repeat(3);
+    let thunks: Vec<Thunk<'_, String>> = vec![
+        Lazy::boxed(create_fn_once("FnOnce")),
+        Lazy::boxed(|| "plain".to_owned() + &suffix),
+        Lazy::boxed(String::new),
+    ];
+
+    // 只访问其中一个，其余的保持未初始化。
+    assert_eq!(*thunks[1], "plain!!!");
+    assert_eq!(thunks[0].get(), None);
+    let all: Vec<&str> = thunks.iter().map(|t| t.force().as_str()).collect();
+    assert_eq!(all, ["This is a: FnOnce", "plain!!!", ""]);
+}
+
+#[test]
+fn panics_poison() {
+    let calls = Cell::new(0);
+    let lazy = Lazy::new(|| -> i32 {
+        calls.set(calls.get() + 1);
+        panic!("boom")
+    });
+
+    let lazy_ref = std::panic::AssertUnwindSafe(&lazy);
+    assert!(std::panic::catch_unwind(|| *lazy_ref.force()).is_err());
+    assert!(lazy.is_poisoned());
+    assert_eq!(lazy.try_force(), Err(LazyError::Poisoned));
+    assert_eq!(format!("{:?}", lazy), "Lazy(<poisoned>)");
+    // 中毒之后不会再调用初始化函数。
+    assert_eq!(calls.get(), 1);
+
+    let sync = SyncLazy::new(|| -> i32 { panic!("boom") });
+    assert!(std::panic::catch_unwind(|| *sync.force()).is_err());
+    assert!(sync.is_poisoned());
+    assert_eq!(sync.try_force(), Err(LazyError::Poisoned));
+}
+
+#[test]
+fn reentrant_access_is_an_error() {
+    thread_local! {
+        // 初始化函数里又访问了自己。
+        static SELF_REFERENCE: Lazy<Result<i32, LazyError>> =
+            Lazy::new(|| SELF_REFERENCE.with(|me| me.try_force().map(|_| 0)));
+    }
+
+    SELF_REFERENCE.with(|lazy| {
+        assert_eq!(lazy.force(), &Err(LazyError::Reentrant));
+        assert!(!lazy.is_poisoned());
+    });
+}
+
+#[test]
+fn sync_lazy_initializes_once_across_threads() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+    use std::time::Duration;
+
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+    static SQUARES: SyncLazy<Vec<u64>> = SyncLazy::new(|| {
+        CALLS.fetch_add(1, Ordering::SeqCst);
+        // 拖长初始化，让其他线程都在等。
+        thread::sleep(Duration::from_millis(20));
+        (0..100).map(|n| n * n).collect()
+    });
+
+    assert_eq!(SQUARES.get(), None);
+    let sums: Vec<u64> = thread::scope(|s| {
+        let handles: Vec<_> = (0..8)
+            .map(|_| s.spawn(|| SQUARES.iter().sum::<u64>()))
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+
+    assert!(sums.iter().all(|&sum| sum == 328_350));
+    assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+    assert_eq!(SQUARES.len(), 100);
+}
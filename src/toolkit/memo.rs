@@ -0,0 +1,483 @@
+/*!
+ * @Author: ZZX
+ * @Description: 记忆化
+ * 扩展 `zc_closure::one`：那里比较了函数、标注类型的闭包和推导类型的闭包，
+ * 它们都是 `Fn(i32) -> i32`。这里的 `memoize`、`memoize_lru` 和 `memoize_rec`
+ * 接受任意 `Fn(A) -> R`，返回一个带缓存的包装，同一个参数只真正计算一次，
+ * 并记录命中、未命中和淘汰的次数。
+ * 缓存放在 `RefCell` 里，所以包装本身也只需要 `&self` 就能调用。
+ * @Date: create in 2026/10/19 5:46 上午
+ */
+
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub hits: u64,
+    pub misses: u64,
+    /// 因为容量不够被挤出缓存的项数。
+    pub evictions: u64,
+}
+
+impl Stats {
+    /// 没有调用过时为 0。
+    pub fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            total => self.hits as f64 / total as f64,
+        }
+    }
+}
+
+pub trait Cache {
+    type Key;
+    type Value;
+
+    fn get(&mut self, key: &Self::Key) -> Option<Self::Value>;
+
+    /// 插入一项，返回因此被挤出去的键。
+    fn insert(&mut self, key: Self::Key, value: Self::Value) -> Option<Self::Key>;
+
+    fn len(&self) -> usize;
+
+    fn clear(&mut self);
+}
+
+/// 不限容量的缓存。
+pub struct Unbounded<K, V> {
+    map: HashMap<K, V>,
+}
+
+impl<K, V> Default for Unbounded<K, V> {
+    fn default() -> Self {
+        Unbounded {
+            map: HashMap::new(),
+        }
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> Cache for Unbounded<K, V> {
+    type Key = K;
+    type Value = V;
+
+    fn get(&mut self, key: &K) -> Option<V> {
+        self.map.get(key).cloned()
+    }
+
+    fn insert(&mut self, key: K, value: V) -> Option<K> {
+        self.map.insert(key, value);
+        None
+    }
+
+    fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    fn clear(&mut self) {
+        self.map.clear();
+    }
+}
+
+/// 最多保留 `capacity` 项，满了就淘汰最久没有用过的一项。
+pub struct Lru<K, V> {
+    capacity: usize,
+    tick: u64,
+    /// 值和最近一次使用的时刻。
+    entries: HashMap<K, (V, u64)>,
+    /// 按使用时刻排序，第一项就是最久没用过的。
+    order: BTreeMap<u64, K>,
+}
+
+impl<K, V> Lru<K, V> {
+    /// # Panics
+    ///
+    /// `capacity` 为 0 时 panic。
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "LRU capacity must be non-zero");
+        Lru {
+            capacity,
+            tick: 0,
+            entries: HashMap::new(),
+            order: BTreeMap::new(),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> Cache for Lru<K, V> {
+    type Key = K;
+    type Value = V;
+
+    fn get(&mut self, key: &K) -> Option<V> {
+        self.tick += 1;
+        let (value, used) = self.entries.get_mut(key)?;
+        self.order.remove(used);
+        *used = self.tick;
+        self.order.insert(self.tick, key.clone());
+        Some(value.clone())
+    }
+
+    fn insert(&mut self, key: K, value: V) -> Option<K> {
+        self.tick += 1;
+        if let Some((_, used)) = self.entries.remove(&key) {
+            self.order.remove(&used);
+        }
+
+        let evicted = if self.entries.len() == self.capacity {
+            let (_, oldest) = self.order.pop_first().expect("full cache has entries");
+            self.entries.remove(&oldest);
+            Some(oldest)
+        } else {
+            None
+        };
+
+        self.order.insert(self.tick, key.clone());
+        self.entries.insert(key, (value, self.tick));
+        evicted
+    }
+
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+    }
+}
+
+/// 缓存和统计，两种包装共用。
+struct Memo<C> {
+    cache: RefCell<C>,
+    stats: Cell<Stats>,
+}
+
+impl<C: Cache> Memo<C>
+where
+    C::Key: Clone,
+{
+    fn new(cache: C) -> Self {
+        Memo {
+            cache: RefCell::new(cache),
+            stats: Cell::new(Stats::default()),
+        }
+    }
+
+    fn get_or_compute<F>(&self, key: C::Key, compute: F) -> C::Value
+    where
+        F: FnOnce(C::Key) -> C::Value,
+        C::Value: Clone,
+    {
+        let mut stats = self.stats.get();
+        // 借用只持续到这一行结束，`compute` 里递归调用时缓存不会被占着。
+        let cached = self.cache.borrow_mut().get(&key);
+        if let Some(value) = cached {
+            stats.hits += 1;
+            self.stats.set(stats);
+            return value;
+        }
+
+        stats.misses += 1;
+        self.stats.set(stats);
+
+        let value = compute(key.clone());
+        if self.cache.borrow_mut().insert(key, value.clone()).is_some() {
+            let mut stats = self.stats.get();
+            stats.evictions += 1;
+            self.stats.set(stats);
+        }
+        value
+    }
+
+    fn clear(&self) {
+        self.cache.borrow_mut().clear();
+    }
+}
+
+/// `memoize` 和 `memoize_lru` 的返回值。
+pub struct Memoized<F, C> {
+    f: F,
+    memo: Memo<C>,
+}
+
+impl<F, C> Memoized<F, C>
+where
+    C: Cache,
+    C::Key: Clone,
+    C::Value: Clone,
+    F: Fn(C::Key) -> C::Value,
+{
+    /// 用自己提供的缓存。
+    pub fn with_cache(cache: C, f: F) -> Self {
+        Memoized {
+            f,
+            memo: Memo::new(cache),
+        }
+    }
+
+    pub fn call(&self, key: C::Key) -> C::Value {
+        self.memo.get_or_compute(key, &self.f)
+    }
+
+    /// 借出一个普通的闭包，可以交给需要 `Fn` 的地方。
+    pub fn as_fn(&self) -> impl Fn(C::Key) -> C::Value + '_ {
+        move |key| self.call(key)
+    }
+
+    pub fn stats(&self) -> Stats {
+        self.memo.stats.get()
+    }
+
+    pub fn len(&self) -> usize {
+        self.memo.cache.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 清空缓存，统计保留。
+    pub fn clear(&self) {
+        self.memo.clear();
+    }
+}
+
+/// `memoize_rec` 的返回值。`f` 的第一个参数用来递归调用，递归的结果同样会被缓存。
+pub struct RecMemoized<F, C> {
+    f: F,
+    memo: Memo<C>,
+}
+
+/// 递归函数里用来调用自己的参数类型。
+pub type Recurse<'a, A, R> = &'a dyn Fn(A) -> R;
+
+impl<F, C> RecMemoized<F, C>
+where
+    C: Cache,
+    C::Key: Clone,
+    C::Value: Clone,
+    F: Fn(Recurse<'_, C::Key, C::Value>, C::Key) -> C::Value,
+{
+    pub fn with_cache(cache: C, f: F) -> Self {
+        RecMemoized {
+            f,
+            memo: Memo::new(cache),
+        }
+    }
+
+    pub fn call(&self, key: C::Key) -> C::Value {
+        self.memo
+            .get_or_compute(key, |key| (self.f)(&|k| self.call(k), key))
+    }
+
+    pub fn as_fn(&self) -> impl Fn(C::Key) -> C::Value + '_ {
+        move |key| self.call(key)
+    }
+
+    pub fn stats(&self) -> Stats {
+        self.memo.stats.get()
+    }
+
+    pub fn len(&self) -> usize {
+        self.memo.cache.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&self) {
+        self.memo.clear();
+    }
+}
+
+/// 缓存 `f` 的所有结果。
+pub fn memoize<A, R, F>(f: F) -> Memoized<F, Unbounded<A, R>>
+where
+    A: Eq + Hash + Clone,
+    R: Clone,
+    F: Fn(A) -> R,
+{
+    Memoized::with_cache(Unbounded::default(), f)
+}
+
+/// 只缓存最近用过的 `capacity` 个结果。
+pub fn memoize_lru<A, R, F>(capacity: usize, f: F) -> Memoized<F, Lru<A, R>>
+where
+    A: Eq + Hash + Clone,
+    R: Clone,
+    F: Fn(A) -> R,
+{
+    Memoized::with_cache(Lru::new(capacity), f)
+}
+
+/// 缓存递归函数，`f` 通过第一个参数调用自己：
+///
+/// ```ignore
+/// let fib = memoize_rec(|fib, n: u64| if n < 2 { n } else { fib(n - 1) + fib(n - 2) });
+/// ```
+pub fn memoize_rec<A, R, F>(f: F) -> RecMemoized<F, Unbounded<A, R>>
+where
+    A: Eq + Hash + Clone,
+    R: Clone,
+    F: Fn(Recurse<'_, A, R>, A) -> R,
+{
+    RecMemoized::with_cache(Unbounded::default(), f)
+}
+
+pub fn memoize_rec_lru<A, R, F>(capacity: usize, f: F) -> RecMemoized<F, Lru<A, R>>
+where
+    A: Eq + Hash + Clone,
+    R: Clone,
+    F: Fn(Recurse<'_, A, R>, A) -> R,
+{
+    RecMemoized::with_cache(Lru::new(capacity), f)
+}
+
+#[cfg(test)]
+fn fib(fib: Recurse<'_, u64, u64>, n: u64) -> u64 {
+    if n < 2 {
+        n
+    } else {
+        fib(n - 1) + fib(n - 2)
+    }
+}
+
+#[test]
+fn each_argument_is_computed_once() {
+    let calls = Cell::new(0);
+    let square = memoize(|x: i32| {
+        calls.set(calls.get() + 1);
+        x * x
+    });
+
+    let results: Vec<i32> = [3, 4, 3, 3, -4, 4]
+        .iter()
+        .map(|&x| square.call(x))
+        .collect();
+    assert_eq!(results, [9, 16, 9, 9, 16, 16]);
+    assert_eq!(calls.get(), 3);
+    assert_eq!(
+        square.stats(),
+        Stats {
+            hits: 3,
+            misses: 3,
+            evictions: 0
+        }
+    );
+    assert_eq!(square.stats().hit_rate(), 0.5);
+    assert_eq!(square.len(), 3);
+
+    square.clear();
+    assert_eq!(square.call(3), 9);
+    assert_eq!(calls.get(), 4);
+    assert_eq!(square.stats().misses, 4);
+}
+
+#[test]
+fn odd_squares_from_zh_hof() {
+    use crate::example::zh_hof::{functional, imperative};
+
+    let sum = memoize(functional);
+    let uppers = [1000, 10, 1000, 1_000_000, 10, 1000];
+    for &upper in &uppers {
+        assert_eq!(sum.call(upper), imperative(upper));
+    }
+    assert_eq!(sum.stats().misses, 3);
+    assert_eq!(sum.stats().hits, 3);
+
+    // 借出的闭包和包装共用一份缓存。
+    let total: u64 = uppers.iter().copied().map(sum.as_fn()).sum();
+    assert_eq!(total, uppers.iter().copied().map(imperative).sum());
+    assert_eq!(sum.stats().hits, 9);
+}
+
+#[test]
+fn lru_evicts_least_recently_used() {
+    let computed = RefCell::new(Vec::new());
+    let negate = memoize_lru(2, |x: i32| {
+        computed.borrow_mut().push(x);
+        -x
+    });
+
+    for x in [1, 2, 1, 3] {
+        negate.call(x);
+    }
+    // 最近用过 1，所以挤掉的是 2。
+    assert_eq!(negate.stats().evictions, 1);
+    assert_eq!(negate.call(1), -1);
+    assert_eq!(negate.call(2), -2);
+    assert_eq!(negate.call(3), -3);
+    assert_eq!(negate.len(), 2);
+    assert_eq!(
+        negate.stats(),
+        Stats {
+            hits: 2,
+            misses: 5,
+            evictions: 3
+        }
+    );
+    assert_eq!(*computed.borrow(), [1, 2, 3, 2, 3]);
+
+    // 对照一个直接的实现：按最近使用的顺序维护一个列表。
+    let mut lru = Lru::new(3);
+    let mut model: Vec<u32> = Vec::new();
+    let mut seed = 7u32;
+    for _ in 0..500 {
+        seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+        let key = (seed >> 16) % 6;
+        let position = model.iter().position(|&k| k == key);
+        assert_eq!(lru.get(&key), position.map(|_| key * 10));
+        if let Some(i) = position {
+            model.remove(i);
+        } else {
+            let expected = if model.len() == 3 {
+                Some(model.remove(0))
+            } else {
+                None
+            };
+            assert_eq!(lru.insert(key, key * 10), expected);
+        }
+        model.push(key);
+        assert_eq!(lru.len(), model.len());
+    }
+}
+
+#[test]
+fn recursive_fibonacci() {
+    let memo = memoize_rec(fib);
+    assert_eq!(memo.call(90), 2_880_067_194_370_816_120);
+    // 0 到 90 各算一次，从 fib(3) 起第二个递归调用都命中。
+    assert_eq!(
+        memo.stats(),
+        Stats {
+            hits: 88,
+            misses: 91,
+            evictions: 0
+        }
+    );
+
+    let calls = memo.stats().hits + memo.stats().misses;
+    assert_eq!(memo.call(50), 12_586_269_025);
+    assert_eq!(memo.stats().hits + memo.stats().misses, calls + 1);
+
+    // 只留三项也足够让递归保持线性。
+    let small = memoize_rec_lru(3, fib);
+    assert_eq!(small.call(90), memo.call(90));
+    assert_eq!(small.stats().misses, 91);
+    assert!(small.len() <= 3);
+
+    let sequence: Vec<u64> = (0..10).map(memoize_rec(fib).as_fn()).collect();
+    assert_eq!(sequence, [0, 1, 1, 2, 3, 5, 8, 13, 21, 34]);
+}
+
+#[test]
+#[should_panic(expected = "LRU capacity must be non-zero")]
+fn zero_capacity_panics() {
+    memoize_lru(0, |x: i32| x);
+}
@@ -0,0 +1,36 @@
+/*!
+ * @Author: ZZX
+ * @Description: 工具库
+ * 在《通过例子学 Rust》各章节示例的基础上扩展出来的可复用模块，
+ * 每个模块的开头注明了它所扩展的章节。
+ * @Date: create in 2026/10/19 5:02 上午
+ */
+
+// 工具库中的大部分项目只在测试里使用。
+#![allow(dead_code)]
+
+pub mod animal_factory;
+pub mod bench;
+pub mod bitset;
+pub mod callbacks;
+pub mod checked_cast;
+pub mod combinators;
+pub mod config;
+pub mod enum_reflect;
+pub mod farm;
+pub mod flags;
+pub mod iter_ext;
+pub mod layout;
+pub mod lazy;
+pub mod memo;
+pub mod number_theory;
+pub mod pipeline;
+pub mod retry;
+pub mod rng;
+pub mod rules;
+pub mod scope_guard;
+pub mod slice_stats;
+pub mod symbolic;
+pub mod transaction;
+pub mod tuple_ext;
+pub mod value;
@@ -0,0 +1,470 @@
+/*!
+ * @Author: ZZX
+ * @Description: 数论
+ * 扩展 `w_match::one`：那里用 `2 | 3 | 5 | 7 | 11` 和 `13..=19`
+ * 两个分支判断素数和“十几岁”的数。这里提供基于筛法的素数判断、
+ * 因数分解、gcd/lcm、完全数/盈数/亏数的判断，
+ * 以及把这些性质汇总成结构化结果的 `classify`。
+ * @Date: create in 2026/10/19 5:02 上午
+ */
+
+use std::convert::TryFrom;
+use std::ops::Range;
+
+/// 埃拉托斯特尼筛法，筛出 `[0, limit]` 内的所有素数。
+pub struct Sieve {
+    composite: Vec<bool>,
+}
+
+impl Sieve {
+    pub fn new(limit: usize) -> Sieve {
+        let mut composite = vec![false; limit + 1];
+        // 0 和 1 既不是素数也不是合数，这里统一标记为“非素数”。
+        for slot in composite.iter_mut().take(2) {
+            *slot = true;
+        }
+
+        let mut i = 2;
+        while i * i <= limit {
+            if !composite[i] {
+                for j in (i * i..=limit).step_by(i) {
+                    composite[j] = true;
+                }
+            }
+            i += 1;
+        }
+
+        Sieve { composite }
+    }
+
+    pub fn limit(&self) -> usize {
+        self.composite.len() - 1
+    }
+
+    /// 超出筛的范围时返回 `None`。
+    pub fn is_prime(&self, n: usize) -> Option<bool> {
+        self.composite.get(n).map(|c| !c)
+    }
+
+    pub fn primes(&self) -> impl Iterator<Item = usize> + '_ {
+        self.composite
+            .iter()
+            .enumerate()
+            .filter(|&(_, c)| !c)
+            .map(|(i, _)| i)
+    }
+}
+
+/// `primes_in` 的基础筛最多筛到这里，再大就逐个做 Miller-Rabin。
+const MAX_BASE_SIEVE: usize = 1 << 24;
+
+/// 分段筛：只为 `range` 分配内存，适合很大但很窄的区间。
+pub fn primes_in(range: Range<u64>) -> Vec<u64> {
+    let Range { start, end } = range;
+    if end <= start {
+        return Vec::new();
+    }
+
+    let root = isqrt(end - 1) as usize;
+    if root > MAX_BASE_SIEVE {
+        // 接近 u64::MAX 时基础筛要筛到 2^32，不如直接测试区间里的每个数。
+        return range.filter(|&n| is_prime(n)).collect();
+    }
+    let base = Sieve::new(root);
+    let mut composite = vec![false; (end - start) as usize];
+
+    for p in base.primes() {
+        let p = p as u64;
+        // 从 `p * p` 或区间内第一个 `p` 的倍数开始划掉，超出 `u64` 就停下。
+        let first = match start.div_ceil(p).checked_mul(p) {
+            Some(m) => std::cmp::max(p * p, m),
+            None => continue,
+        };
+        let mut m = Some(first);
+        while let Some(n) = m.filter(|&n| n < end) {
+            composite[(n - start) as usize] = true;
+            m = n.checked_add(p);
+        }
+    }
+
+    composite
+        .iter()
+        .enumerate()
+        .map(|(i, &c)| (start + i as u64, c))
+        .filter(|&(n, c)| n >= 2 && !c)
+        .map(|(n, _)| n)
+        .collect()
+}
+
+/// 对任意 `u64` 做确定性的 Miller-Rabin 素性测试。
+pub fn is_prime(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+    for &p in &[2u64, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37] {
+        if n.is_multiple_of(p) {
+            return n == p;
+        }
+    }
+
+    let mut d = n - 1;
+    let mut s = 0;
+    while d.is_multiple_of(2) {
+        d /= 2;
+        s += 1;
+    }
+
+    // 这组底数对所有 64 位整数都是确定性的。
+    'witness: for &a in &[2u64, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37] {
+        let mut x = pow_mod(a, d, n);
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+        for _ in 1..s {
+            x = mul_mod(x, x, n);
+            if x == n - 1 {
+                continue 'witness;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    ((a as u128 * b as u128) % m as u128) as u64
+}
+
+fn pow_mod(mut base: u64, mut exp: u64, m: u64) -> u64 {
+    let mut result = 1;
+    base %= m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exp >>= 1;
+    }
+    result
+}
+
+fn isqrt(n: u64) -> u64 {
+    let mut r = (n as f64).sqrt() as u64;
+    // 浮点开方可能差一，修正到 `r * r <= n < (r + 1) * (r + 1)`。
+    // 在 `u128` 里比较，`n` 接近 `u64::MAX` 时 `(r + 1) * (r + 1)` 不会溢出。
+    let square = |r: u64| r as u128 * r as u128;
+    while square(r) > n as u128 {
+        r -= 1;
+    }
+    while square(r + 1) <= n as u128 {
+        r += 1;
+    }
+    r
+}
+
+/// 质因数分解，返回按底数升序排列的 `(素数, 指数)`。`0` 和 `1` 返回空表。
+pub fn factorize(mut n: u64) -> Vec<(u64, u32)> {
+    let mut factors = Vec::new();
+    if n < 2 {
+        return factors;
+    }
+
+    // 返回是否除掉了 `p`。
+    let mut push = |n: &mut u64, p: u64| {
+        let mut exp = 0;
+        while n.is_multiple_of(p) {
+            *n /= p;
+            exp += 1;
+        }
+        if exp > 0 {
+            factors.push((p, exp));
+        }
+        exp > 0
+    };
+
+    push(&mut n, 2);
+    push(&mut n, 3);
+    // 6k ± 1 轮式试除。`n` 变小之后如果剩下的是素数就直接结束，
+    // 不用一直试除到它的平方根；用 `p <= n / p` 而不是 `p * p <= n`，`p` 超过 2^32 时也不会溢出。
+    let mut remaining_is_prime = is_prime(n);
+    let mut p = 5;
+    while !remaining_is_prime && p <= n / p {
+        if push(&mut n, p) | push(&mut n, p + 2) {
+            remaining_is_prime = is_prime(n);
+        }
+        p += 6;
+    }
+    if n > 1 {
+        factors.push((n, 1));
+    }
+    factors
+}
+
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        let t = a % b;
+        a = b;
+        b = t;
+    }
+    a
+}
+
+/// 结果溢出 `u64` 时返回 `None`。
+pub fn lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// 由质因数分解计算所有正因数之和。`p^e <= n < 2^64`，所以每一项和乘积都放得进 `u128`。
+fn sigma(factors: &[(u64, u32)]) -> u128 {
+    factors
+        .iter()
+        .map(|&(p, e)| (0..=e).map(|k| (p as u128).pow(k)).sum::<u128>())
+        .product()
+}
+
+/// 所有正因数之和（包括 `n` 本身），结果超出 `u64` 时返回 `None`。
+pub fn divisor_sum(n: u64) -> Option<u64> {
+    if n == 0 {
+        return Some(0);
+    }
+    u64::try_from(sigma(&factorize(n))).ok()
+}
+
+/// 按真因数之和与 `n` 的大小关系分类。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Abundance {
+    Deficient,
+    Perfect,
+    Abundant,
+}
+
+/// `n >= 1` 时返回分类，`0` 没有意义，返回 `None`。
+pub fn abundance(n: u64) -> Option<Abundance> {
+    if n == 0 {
+        return None;
+    }
+    Some(abundance_from_sigma(n, sigma(&factorize(n))))
+}
+
+fn abundance_from_sigma(n: u64, sigma: u128) -> Abundance {
+    let proper = sigma - n as u128;
+    match proper.cmp(&(n as u128)) {
+        std::cmp::Ordering::Less => Abundance::Deficient,
+        std::cmp::Ordering::Equal => Abundance::Perfect,
+        std::cmp::Ordering::Greater => Abundance::Abundant,
+    }
+}
+
+/// 数的“素性”。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Primality {
+    Zero,
+    One,
+    Prime,
+    Composite(Vec<(u64, u32)>),
+}
+
+/// `classify` 的结果：`w_match::one` 中各个分支关心的所有性质。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Classification {
+    pub n: u64,
+    pub primality: Primality,
+    pub abundance: Option<Abundance>,
+    pub even: bool,
+    pub square: bool,
+    pub teen: bool,
+}
+
+pub fn classify(n: u64) -> Classification {
+    // 只分解一次：素数的真因数之和是 1，合数复用分解的结果。
+    let (primality, abundance) = match n {
+        0 => (Primality::Zero, None),
+        1 => (Primality::One, Some(Abundance::Deficient)),
+        n if is_prime(n) => (Primality::Prime, Some(Abundance::Deficient)),
+        n => {
+            let factors = factorize(n);
+            let abundance = abundance_from_sigma(n, sigma(&factors));
+            (Primality::Composite(factors), Some(abundance))
+        }
+    };
+    let root = isqrt(n);
+
+    Classification {
+        n,
+        primality,
+        abundance,
+        even: n.is_multiple_of(2),
+        square: root * root == n,
+        teen: (13..=19).contains(&n),
+    }
+}
+
+impl Classification {
+    pub fn is_prime(&self) -> bool {
+        self.primality == Primality::Prime
+    }
+}
+
+// 前 25 个素数（100 以内）。
+const PRIMES_BELOW_100: [u64; 25] = [
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
+];
+
+// 100 以内的盈数。
+const ABUNDANT_BELOW_100: [u64; 21] = [
+    12, 18, 20, 24, 30, 36, 40, 42, 48, 54, 56, 60, 66, 70, 72, 78, 80, 84, 88, 90, 96,
+];
+
+const PERFECT_NUMBERS: [u64; 5] = [6, 28, 496, 8128, 33_550_336];
+
+#[test]
+fn sieve_matches_prime_table() {
+    let sieve = Sieve::new(100);
+    let primes: Vec<u64> = sieve.primes().map(|p| p as u64).collect();
+    assert_eq!(primes, PRIMES_BELOW_100);
+
+    assert_eq!(sieve.limit(), 100);
+    assert_eq!(sieve.is_prime(97), Some(true));
+    assert_eq!(sieve.is_prime(1), Some(false));
+    assert_eq!(sieve.is_prime(101), None);
+}
+
+#[test]
+fn prime_counting_function() {
+    // π(10^k) 的已知值。
+    for &(limit, count) in &[(10, 4), (1_000, 168), (100_000, 9_592), (1_000_000, 78_498)] {
+        assert_eq!(Sieve::new(limit).primes().count(), count);
+    }
+}
+
+#[test]
+fn segmented_sieve_agrees_with_miller_rabin() {
+    let lo = 1_000_000_000_000;
+    let segment = primes_in(lo..lo + 10_000);
+    let expected: Vec<u64> = (lo..lo + 10_000).filter(|&n| is_prime(n)).collect();
+    assert_eq!(segment, expected);
+
+    assert_eq!(primes_in(0..100), PRIMES_BELOW_100);
+    assert!(primes_in(50..50).is_empty());
+}
+
+#[test]
+fn miller_rabin_on_known_values() {
+    for n in 0..100 {
+        assert_eq!(is_prime(n), PRIMES_BELOW_100.contains(&n), "n = {}", n);
+    }
+    // 最大的 64 位素数、梅森素数 2^61 - 1 和一个强伪素数（底 2）。
+    assert!(is_prime(18_446_744_073_709_551_557));
+    assert!(is_prime((1 << 61) - 1));
+    assert!(!is_prime(3_215_031_751));
+}
+
+#[test]
+fn factorization_round_trips() {
+    assert_eq!(factorize(0), vec![]);
+    assert_eq!(factorize(1), vec![]);
+    assert_eq!(factorize(360), vec![(2, 3), (3, 2), (5, 1)]);
+    assert_eq!(factorize(9_999_991), vec![(9_999_991, 1)]);
+
+    for n in 2..2_000u64 {
+        let factors = factorize(n);
+        let product: u64 = factors.iter().map(|&(p, e)| p.pow(e)).product();
+        assert_eq!(product, n);
+        assert!(factors.iter().all(|&(p, _)| is_prime(p)));
+    }
+}
+
+#[test]
+fn gcd_and_lcm() {
+    assert_eq!(gcd(0, 0), 0);
+    assert_eq!(gcd(0, 7), 7);
+    assert_eq!(gcd(48, 18), 6);
+    assert_eq!(lcm(4, 6), Some(12));
+    assert_eq!(lcm(0, 6), Some(0));
+    assert_eq!(lcm(u64::MAX, u64::MAX - 1), None);
+
+    for a in 1..50u64 {
+        for b in 1..50u64 {
+            assert_eq!(gcd(a, b) * lcm(a, b).unwrap(), a * b);
+        }
+    }
+}
+
+#[test]
+fn abundance_matches_tables() {
+    for n in 1..100u64 {
+        let expected = if ABUNDANT_BELOW_100.contains(&n) {
+            Abundance::Abundant
+        } else if PERFECT_NUMBERS.contains(&n) {
+            Abundance::Perfect
+        } else {
+            Abundance::Deficient
+        };
+        assert_eq!(abundance(n), Some(expected), "n = {}", n);
+    }
+    for &n in &PERFECT_NUMBERS {
+        assert_eq!(abundance(n), Some(Abundance::Perfect));
+    }
+    assert_eq!(abundance(0), None);
+}
+
+#[test]
+fn classify_thirteen() {
+    let c = classify(13);
+    assert!(c.is_prime());
+    assert!(c.teen);
+    assert!(!c.even);
+    assert!(!c.square);
+    assert_eq!(c.abundance, Some(Abundance::Deficient));
+
+    let c = classify(36);
+    assert_eq!(c.primality, Primality::Composite(vec![(2, 2), (3, 2)]));
+    assert_eq!(c.abundance, Some(Abundance::Abundant));
+    assert!(c.square && c.even && !c.teen);
+
+    assert_eq!(classify(0).primality, Primality::Zero);
+    assert_eq!(classify(1).primality, Primality::One);
+}
+
+#[test]
+fn extreme_values() {
+    // u64::MAX = 3 · 5 · 17 · 257 · 641 · 65537 · 6700417
+    let factors = vec![
+        (3, 1),
+        (5, 1),
+        (17, 1),
+        (257, 1),
+        (641, 1),
+        (65_537, 1),
+        (6_700_417, 1),
+    ];
+    assert_eq!(factorize(u64::MAX), factors);
+    let c = classify(u64::MAX);
+    assert_eq!(c.primality, Primality::Composite(factors));
+    assert_eq!(c.abundance, Some(Abundance::Deficient));
+    assert!(!c.square && !c.even);
+    assert_eq!(isqrt(u64::MAX), u32::MAX as u64);
+
+    // 2^63 - 25 是小于 2^63 的最大素数。
+    let p = (1u64 << 63) - 25;
+    assert!(is_prime(p));
+    assert_eq!(factorize(p), vec![(p, 1)]);
+    assert_eq!(factorize(2 * p), vec![(2, 1), (p, 1)]);
+    assert!(classify(p).is_prime());
+    assert_eq!(divisor_sum(p), Some(p + 1));
+
+    // σ(3 · 2^62) = 4 · (2^63 - 1) 超出了 u64，但分类仍然可以计算。
+    let n = 3u64 << 62;
+    assert_eq!(divisor_sum(n), None);
+    assert_eq!(abundance(n), Some(Abundance::Abundant));
+    assert_eq!(divisor_sum(12), Some(28));
+    assert_eq!(divisor_sum(0), Some(0));
+
+    // 区间贴着 u64::MAX 时不会溢出，也不会去筛到 2^32。
+    let top = primes_in(u64::MAX - 100..u64::MAX);
+    assert_eq!(top, vec![u64::MAX - 94, u64::MAX - 82, u64::MAX - 58]);
+    assert_eq!(primes_in(u64::MAX - 10..u64::MAX), Vec::<u64>::new());
+}
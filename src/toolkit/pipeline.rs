@@ -0,0 +1,465 @@
+/*!
+ * @Author: ZZX
+ * @Description: 闭包流水线
+ * 扩展 `ze_closure_input_params::one`：那里的 `apply` 和 `apply_to_3` 各自只调用一次闭包。
+ * `Pipeline` 把一串 `Fn`/`FnMut` 闭包串起来，任何一步返回 `Err` 都会立即停下，
+ * 还可以在已有的步骤外面包一层 before/after 中间件。
+ * `Pipeline::new()` 把每一步都编进类型里，全部静态分发；
+ * `Pipeline::boxed()` 把每一步装箱，类型不随步骤增长，可以在运行时拼装。
+ * @Date: create in 2026/10/19 5:46 上午
+ */
+
+use std::marker::PhantomData;
+
+/// 流水线中的一步。所有 `FnMut(T) -> Result<T, E>` 闭包都是一步。
+pub trait Stage<T, E> {
+    fn run(&mut self, input: T) -> Result<T, E>;
+}
+
+impl<T, E, F> Stage<T, E> for F
+where
+    F: FnMut(T) -> Result<T, E>,
+{
+    fn run(&mut self, input: T) -> Result<T, E> {
+        self(input)
+    }
+}
+
+/// 原样返回输入，空流水线的起点。
+pub struct Identity;
+
+impl<T, E> Stage<T, E> for Identity {
+    fn run(&mut self, input: T) -> Result<T, E> {
+        Ok(input)
+    }
+}
+
+/// 先执行 `first`，成功了再执行 `second`。
+pub struct Then<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<T, E, A, B> Stage<T, E> for Then<A, B>
+where
+    A: Stage<T, E>,
+    B: Stage<T, E>,
+{
+    fn run(&mut self, input: T) -> Result<T, E> {
+        let middle = self.first.run(input)?;
+        self.second.run(middle)
+    }
+}
+
+/// 不会失败的一步。
+pub struct Map<F> {
+    f: F,
+}
+
+impl<T, E, F> Stage<T, E> for Map<F>
+where
+    F: FnMut(T) -> T,
+{
+    fn run(&mut self, input: T) -> Result<T, E> {
+        Ok((self.f)(input))
+    }
+}
+
+/// 中间件：拿到输入和被包住的 `inner`，自己决定是否、如何调用它。
+pub struct Around<S, M> {
+    inner: S,
+    middleware: M,
+}
+
+impl<T, E, S, M> Stage<T, E> for Around<S, M>
+where
+    S: Stage<T, E>,
+    M: FnMut(T, &mut S) -> Result<T, E>,
+{
+    fn run(&mut self, input: T) -> Result<T, E> {
+        (self.middleware)(input, &mut self.inner)
+    }
+}
+
+pub struct Before<S, F> {
+    inner: S,
+    f: F,
+}
+
+impl<T, E, S, F> Stage<T, E> for Before<S, F>
+where
+    S: Stage<T, E>,
+    F: FnMut(&T),
+{
+    fn run(&mut self, input: T) -> Result<T, E> {
+        (self.f)(&input);
+        self.inner.run(input)
+    }
+}
+
+pub struct After<S, F> {
+    inner: S,
+    f: F,
+}
+
+impl<T, E, S, F> Stage<T, E> for After<S, F>
+where
+    S: Stage<T, E>,
+    F: FnMut(&Result<T, E>),
+{
+    fn run(&mut self, input: T) -> Result<T, E> {
+        let output = self.inner.run(input);
+        (self.f)(&output);
+        output
+    }
+}
+
+/// 静态分发的流水线，每加一步类型就多一层。
+pub struct Pipeline<T, E, S> {
+    stage: S,
+    marker: PhantomData<fn(T) -> Result<T, E>>,
+}
+
+impl<T, E> Pipeline<T, E, Identity> {
+    pub fn new() -> Self {
+        Pipeline::from_stage(Identity)
+    }
+
+    /// 每一步都装箱的流水线。
+    pub fn boxed<'a>() -> BoxedPipeline<'a, T, E> {
+        BoxedPipeline::new()
+    }
+}
+
+impl<T, E> Default for Pipeline<T, E, Identity> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, E, S: Stage<T, E>> Pipeline<T, E, S> {
+    pub fn from_stage(stage: S) -> Self {
+        Pipeline {
+            stage,
+            marker: PhantomData,
+        }
+    }
+
+    /// 追加可能失败的一步。
+    pub fn then<F>(self, f: F) -> Pipeline<T, E, Then<S, F>>
+    where
+        F: FnMut(T) -> Result<T, E>,
+    {
+        Pipeline::from_stage(Then {
+            first: self.stage,
+            second: f,
+        })
+    }
+
+    /// 追加不会失败的一步。
+    pub fn map<F>(self, f: F) -> Pipeline<T, E, Then<S, Map<F>>>
+    where
+        F: FnMut(T) -> T,
+    {
+        Pipeline::from_stage(Then {
+            first: self.stage,
+            second: Map { f },
+        })
+    }
+
+    /// 用中间件包住目前为止的所有步骤，之后追加的步骤不受影响。
+    /// `middleware` 的第二个参数就是被包住的部分，用 `next.run(input)` 调用。
+    pub fn around<M>(self, middleware: M) -> Pipeline<T, E, Around<S, M>>
+    where
+        M: FnMut(T, &mut S) -> Result<T, E>,
+    {
+        Pipeline::from_stage(Around {
+            inner: self.stage,
+            middleware,
+        })
+    }
+
+    /// 在目前为止的步骤之前看一眼输入。
+    pub fn before<F>(self, f: F) -> Pipeline<T, E, Before<S, F>>
+    where
+        F: FnMut(&T),
+    {
+        Pipeline::from_stage(Before {
+            inner: self.stage,
+            f,
+        })
+    }
+
+    /// 在目前为止的步骤之后看一眼结果，失败时也会调用。
+    pub fn after<F>(self, f: F) -> Pipeline<T, E, After<S, F>>
+    where
+        F: FnMut(&Result<T, E>),
+    {
+        Pipeline::from_stage(After {
+            inner: self.stage,
+            f,
+        })
+    }
+
+    pub fn run(&mut self, input: T) -> Result<T, E> {
+        self.stage.run(input)
+    }
+
+    /// 转成普通的 `FnMut` 闭包。
+    pub fn into_fn(mut self) -> impl FnMut(T) -> Result<T, E> {
+        move |input| self.run(input)
+    }
+
+    /// 绑定输入，得到一个只能调用一次的 `FnOnce`。
+    pub fn bind(mut self, input: T) -> impl FnOnce() -> Result<T, E> {
+        move || self.run(input)
+    }
+
+    /// 把整条流水线装进一个箱子，之后可以继续按运行时的需要追加步骤。
+    pub fn into_boxed<'a>(self) -> BoxedPipeline<'a, T, E>
+    where
+        S: 'a,
+    {
+        let mut stage = self.stage;
+        BoxedPipeline::new().then(move |input| stage.run(input))
+    }
+}
+
+impl<T, E, S: Stage<T, E>> Stage<T, E> for Pipeline<T, E, S> {
+    fn run(&mut self, input: T) -> Result<T, E> {
+        self.stage.run(input)
+    }
+}
+
+type BoxedStage<'a, T, E> = Box<dyn FnMut(T) -> Result<T, E> + 'a>;
+
+/// 动态分发的流水线，类型不随步骤增长。
+pub struct BoxedPipeline<'a, T, E> {
+    stages: Vec<BoxedStage<'a, T, E>>,
+}
+
+impl<'a, T, E> BoxedPipeline<'a, T, E> {
+    pub fn new() -> Self {
+        BoxedPipeline { stages: Vec::new() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.stages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    pub fn then<F>(mut self, f: F) -> Self
+    where
+        F: FnMut(T) -> Result<T, E> + 'a,
+    {
+        self.stages.push(Box::new(f));
+        self
+    }
+
+    pub fn map<F>(self, mut f: F) -> Self
+    where
+        F: FnMut(T) -> T + 'a,
+    {
+        self.then(move |input| Ok(f(input)))
+    }
+
+    /// 和 `Pipeline::around` 一样，只是被包住的部分是 `dyn Stage`。
+    /// 包住之后目前为止的所有步骤合成一步。
+    pub fn around<M>(self, mut middleware: M) -> Self
+    where
+        M: FnMut(T, &mut dyn Stage<T, E>) -> Result<T, E> + 'a,
+        T: 'a,
+        E: 'a,
+    {
+        let mut inner = self;
+        BoxedPipeline::new().then(move |input| middleware(input, &mut inner))
+    }
+
+    pub fn before<F>(self, mut f: F) -> Self
+    where
+        F: FnMut(&T) + 'a,
+        T: 'a,
+        E: 'a,
+    {
+        self.around(move |input, next| {
+            f(&input);
+            next.run(input)
+        })
+    }
+
+    pub fn after<F>(self, mut f: F) -> Self
+    where
+        F: FnMut(&Result<T, E>) + 'a,
+        T: 'a,
+        E: 'a,
+    {
+        self.around(move |input, next| {
+            let output = next.run(input);
+            f(&output);
+            output
+        })
+    }
+
+    pub fn run(&mut self, input: T) -> Result<T, E> {
+        self.stages
+            .iter_mut()
+            .try_fold(input, |value, stage| stage(value))
+    }
+}
+
+impl<T, E> Default for BoxedPipeline<'_, T, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, E> Stage<T, E> for BoxedPipeline<'_, T, E> {
+    fn run(&mut self, input: T) -> Result<T, E> {
+        BoxedPipeline::run(self, input)
+    }
+}
+
+#[cfg(test)]
+fn parse_positive(s: String) -> Result<String, String> {
+    match s.trim().parse::<i64>() {
+        Ok(n) if n > 0 => Ok(n.to_string()),
+        Ok(n) => Err(format!("{} is not positive", n)),
+        Err(e) => Err(format!("{:?}: {}", s, e)),
+    }
+}
+
+#[test]
+fn fn_and_fn_mut_stages() {
+    let offset = 10;
+    // `Fn`：只读地捕获 `offset`。
+    let add_offset = |x: i32| Ok::<_, String>(x + offset);
+    // `FnMut`：每调用一次计数加一。
+    let mut calls = 0;
+    let count = |x: i32| {
+        calls += 1;
+        Ok(x)
+    };
+
+    let mut pipeline = Pipeline::new().then(add_offset).then(count).map(|x| x * 2);
+    assert_eq!(pipeline.run(1), Ok(22));
+    assert_eq!(pipeline.run(5), Ok(30));
+    assert_eq!(calls, 2);
+
+    // 空流水线原样返回。
+    assert_eq!(Pipeline::<_, (), _>::new().run("same"), Ok("same"));
+}
+
+#[test]
+fn errors_short_circuit() {
+    let mut reached = Vec::new();
+    let mut pipeline = Pipeline::new()
+        .map(|s: String| s.replace('_', ""))
+        .then(parse_positive)
+        .then(|s| {
+            reached.push(s.clone());
+            Ok(s + "!")
+        });
+
+    assert_eq!(pipeline.run("1_000".to_string()), Ok("1000!".to_string()));
+    assert_eq!(
+        pipeline.run("-3".to_string()),
+        Err("-3 is not positive".to_string())
+    );
+    assert!(pipeline.run("x".to_string()).is_err());
+    assert_eq!(reached, ["1000"]);
+}
+
+#[test]
+fn middleware_wraps_earlier_stages() {
+    use std::cell::RefCell;
+
+    // 两个中间件都要写日志，所以用 `RefCell` 共享。
+    let log = RefCell::new(Vec::new());
+    let mut pipeline = Pipeline::new()
+        .map(|x: i32| x + 1)
+        .before(|x| log.borrow_mut().push(format!("before {}", x)))
+        .after(|r: &Result<i32, String>| log.borrow_mut().push(format!("after {:?}", r)))
+        .then(|x| Ok(x * 10));
+    assert_eq!(pipeline.run(1), Ok(20));
+    // `then` 在中间件之后追加，不在包裹范围内。
+    assert_eq!(log.into_inner(), ["before 1", "after Ok(2)"]);
+
+    // `around` 可以改写输入、跳过内部或者处理错误。
+    let mut inner_calls = 0;
+    let mut pipeline = Pipeline::new()
+        .then(|x: i32| {
+            inner_calls += 1;
+            if x < 100 {
+                Ok(x)
+            } else {
+                Err("too big")
+            }
+        })
+        .around(|x, next| match x {
+            0 => Ok(0),
+            x => next.run(x.abs()).or(Ok(99)),
+        });
+    assert_eq!(pipeline.run(0), Ok(0));
+    assert_eq!(pipeline.run(-5), Ok(5));
+    assert_eq!(pipeline.run(500), Ok(99));
+    assert_eq!(inner_calls, 2);
+}
+
+#[test]
+fn boxed_pipeline_matches_static() {
+    let build_static = || {
+        Pipeline::new()
+            .map(|s: String| s.replace('_', ""))
+            .then(parse_positive)
+            .around(|s, next| next.run(s).map_err(|e| format!("rejected: {}", e)))
+    };
+
+    // 步骤个数在运行时才决定。
+    let mut boxed = Pipeline::boxed().map(|s: String| s.replace('_', ""));
+    for _ in 0..3 {
+        boxed = boxed.then(parse_positive);
+    }
+    let mut boxed = boxed.around(|s, next| next.run(s).map_err(|e| format!("rejected: {}", e)));
+    assert_eq!(boxed.len(), 1);
+
+    let mut converted = build_static().into_boxed().map(|s| s + "?");
+    let mut fixed = build_static();
+    for input in ["12", "1_2", "0", "", "-7", "x"] {
+        let expected = fixed.run(input.to_string());
+        assert_eq!(boxed.run(input.to_string()), expected);
+        assert_eq!(converted.run(input.to_string()), expected.map(|s| s + "?"));
+    }
+
+    // 流水线本身也是一步，可以嵌进另一条流水线。
+    let mut outer = Pipeline::from_stage(boxed).map(|s| s + "!");
+    assert_eq!(outer.run("1_2".to_string()), Ok("12!".to_string()));
+}
+
+#[test]
+fn into_closures() {
+    fn apply<F: FnOnce() -> Result<i32, String>>(f: F) -> Result<i32, String> {
+        f()
+    }
+
+    fn apply_to_3<F: FnMut(i32) -> Result<i32, String>>(mut f: F) -> Result<i32, String> {
+        f(3)
+    }
+
+    let double = || Pipeline::new().map(|x: i32| 2 * x);
+
+    // `FnOnce`：绑定输入之后只能调用一次。
+    assert_eq!(apply(double().bind(21)), Ok(42));
+    assert_eq!(apply_to_3(double().into_fn()), Ok(6));
+
+    let mut seen = Vec::new();
+    let results: Result<Vec<i32>, String> = [1, 2, 3]
+        .iter()
+        .copied()
+        .map(double().before(|x| seen.push(*x)).into_fn())
+        .collect();
+    assert_eq!(results, Ok(vec![2, 4, 6]));
+    assert_eq!(seen, [1, 2, 3]);
+}
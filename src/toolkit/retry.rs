@@ -0,0 +1,461 @@
+/*!
+ * @Author: ZZX
+ * @Description: 重试与轮询
+ * 推广 `z_while_let::one` 和 `two`：那里手写循环不断修改 `Option<i32>` 直到超过阈值。
+ * `Retry` 反复调用一个返回 `Option` 或 `Result` 的闭包，直到成功、次数用完、
+ * 超时或被取消，两次调用之间按固定、线性或指数退避等待，
+ * 最后返回一份记录了每次等待的报告。时间来自可替换的 `Clock`，
+ * 测试里用 `FakeClock`，不会真的睡眠。
+ * @Date: create in 2026/10/19 5:46 上午
+ */
+
+use std::cell::{Cell, RefCell};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// 时间来源。`sleep` 只在两次尝试之间调用。
+pub trait Clock {
+    /// 从某个固定起点开始经过的时间。
+    fn now(&self) -> Duration;
+    fn sleep(&self, duration: Duration);
+}
+
+/// 真实的时钟，会阻塞当前线程。
+pub struct SystemClock {
+    start: Instant,
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        SystemClock {
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+/// 测试用的时钟：`sleep` 只是把时间往前拨，并记下每次睡了多久。
+#[derive(Default)]
+pub struct FakeClock {
+    now: Cell<Duration>,
+    sleeps: RefCell<Vec<Duration>>,
+}
+
+impl FakeClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 模拟被重试的操作本身花掉的时间。
+    pub fn advance(&self, duration: Duration) {
+        self.now.set(self.now.get() + duration);
+    }
+
+    pub fn sleeps(&self) -> Vec<Duration> {
+        self.sleeps.borrow().clone()
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> Duration {
+        self.now.get()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        self.advance(duration);
+        self.sleeps.borrow_mut().push(duration);
+    }
+}
+
+/// 第 n 次失败之后等待多久。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backoff {
+    /// 不等待，立刻重试。
+    Immediate,
+    Fixed(Duration),
+    /// `initial`, `initial + step`, `initial + 2 * step`, ...
+    Linear {
+        initial: Duration,
+        step: Duration,
+    },
+    /// `initial`, `initial * factor`, `initial * factor²`, ...，不超过 `max`。
+    Exponential {
+        initial: Duration,
+        factor: u32,
+        max: Duration,
+    },
+}
+
+impl Backoff {
+    /// 第 `failures` 次失败（从 1 开始）之后的等待时间。
+    pub fn delay(&self, failures: u32) -> Duration {
+        let n = failures.saturating_sub(1);
+        match *self {
+            Backoff::Immediate => Duration::ZERO,
+            Backoff::Fixed(delay) => delay,
+            Backoff::Linear { initial, step } => step
+                .checked_mul(n)
+                .and_then(|extra| initial.checked_add(extra))
+                .unwrap_or(Duration::MAX),
+            Backoff::Exponential {
+                initial,
+                factor,
+                max,
+            } => factor
+                .checked_pow(n)
+                .and_then(|scale| initial.checked_mul(scale))
+                .map_or(max, |delay| delay.min(max)),
+        }
+    }
+}
+
+/// 可以在别的线程或被重试的闭包里取消重试。
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+/// 一次尝试的结果：`Option` 的失败没有内容，`Result` 的失败是它的错误。
+pub trait Attempt {
+    type Output;
+    type Failure;
+    fn into_result(self) -> Result<Self::Output, Self::Failure>;
+}
+
+impl<T> Attempt for Option<T> {
+    type Output = T;
+    type Failure = ();
+
+    fn into_result(self) -> Result<T, ()> {
+        self.ok_or(())
+    }
+}
+
+impl<T, E> Attempt for Result<T, E> {
+    type Output = T;
+    type Failure = E;
+
+    fn into_result(self) -> Result<T, E> {
+        self
+    }
+}
+
+/// 没能成功时停下来的原因，带着最后一次失败（如果有）。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Stopped<F> {
+    /// 用完了 `max_attempts` 次。
+    Exhausted(F),
+    /// 再等下去会超过 `timeout`。
+    TimedOut(F),
+    /// 被 `CancelToken` 取消；在第一次尝试之前取消时没有失败记录。
+    Cancelled(Option<F>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report<T, F> {
+    pub result: Result<T, Stopped<F>>,
+    /// 调用闭包的次数。
+    pub attempts: u32,
+    /// 每两次尝试之间等待的时间。
+    pub delays: Vec<Duration>,
+    pub elapsed: Duration,
+}
+
+impl<T, F> Report<T, F> {
+    pub fn is_success(&self) -> bool {
+        self.result.is_ok()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Retry {
+    max_attempts: Option<u32>,
+    backoff: Backoff,
+    timeout: Option<Duration>,
+    cancel: Option<CancelToken>,
+}
+
+impl Default for Retry {
+    /// 最多 3 次，不等待。
+    fn default() -> Self {
+        Retry {
+            max_attempts: Some(3),
+            backoff: Backoff::Immediate,
+            timeout: None,
+            cancel: None,
+        }
+    }
+}
+
+impl Retry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 以固定间隔轮询，不限次数；通常再配上 `timeout` 或 `cancel_token`。
+    pub fn poll(interval: Duration) -> Self {
+        Retry {
+            max_attempts: None,
+            backoff: Backoff::Fixed(interval),
+            ..Self::default()
+        }
+    }
+
+    /// `0` 按 `1` 处理：至少尝试一次。
+    pub fn max_attempts(mut self, n: u32) -> Self {
+        self.max_attempts = Some(n.max(1));
+        self
+    }
+
+    pub fn unlimited(mut self) -> Self {
+        self.max_attempts = None;
+        self
+    }
+
+    pub fn backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// 从开始到最后一次尝试的总时间上限。
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn cancel_token(mut self, token: CancelToken) -> Self {
+        self.cancel = Some(token);
+        self
+    }
+
+    fn cancelled(&self) -> bool {
+        self.cancel.as_ref().is_some_and(CancelToken::is_cancelled)
+    }
+
+    /// 反复调用 `f`（参数是从 1 开始的尝试次数），直到它成功或者需要停下。
+    pub fn run<C, A, F>(&self, clock: &C, mut f: F) -> Report<A::Output, A::Failure>
+    where
+        C: Clock,
+        A: Attempt,
+        F: FnMut(u32) -> A,
+    {
+        let start = clock.now();
+        let mut attempts = 0;
+        let mut delays = Vec::new();
+        let mut last_failure = None;
+
+        let result = loop {
+            if self.cancelled() {
+                break Err(Stopped::Cancelled(last_failure));
+            }
+
+            attempts += 1;
+            let failure = match f(attempts).into_result() {
+                Ok(value) => break Ok(value),
+                Err(failure) => failure,
+            };
+
+            if self.max_attempts.is_some_and(|max| attempts >= max) {
+                break Err(Stopped::Exhausted(failure));
+            }
+            if self.cancelled() {
+                break Err(Stopped::Cancelled(Some(failure)));
+            }
+
+            let delay = self.backoff.delay(attempts);
+            let elapsed = clock.now() - start;
+            if self
+                .timeout
+                .is_some_and(|timeout| elapsed.saturating_add(delay) > timeout)
+            {
+                break Err(Stopped::TimedOut(failure));
+            }
+
+            if !delay.is_zero() {
+                clock.sleep(delay);
+            }
+            delays.push(delay);
+            last_failure = Some(failure);
+        };
+
+        Report {
+            result,
+            attempts,
+            delays,
+            elapsed: clock.now() - start,
+        }
+    }
+}
+
+#[cfg(test)]
+fn ms(n: u64) -> Duration {
+    Duration::from_millis(n)
+}
+
+#[test]
+fn backoff_delays() {
+    let delays = |backoff: Backoff| (1..=6).map(|n| backoff.delay(n)).collect::<Vec<_>>();
+
+    assert_eq!(delays(Backoff::Immediate), vec![ms(0); 6]);
+    assert_eq!(delays(Backoff::Fixed(ms(5))), vec![ms(5); 6]);
+    assert_eq!(
+        delays(Backoff::Linear {
+            initial: ms(10),
+            step: ms(5)
+        }),
+        vec![ms(10), ms(15), ms(20), ms(25), ms(30), ms(35)]
+    );
+    assert_eq!(
+        delays(Backoff::Exponential {
+            initial: ms(10),
+            factor: 2,
+            max: ms(100)
+        }),
+        vec![ms(10), ms(20), ms(40), ms(80), ms(100), ms(100)]
+    );
+
+    // 溢出时停在上限。
+    let huge = Backoff::Exponential {
+        initial: ms(1),
+        factor: 10,
+        max: Duration::from_secs(60),
+    };
+    assert_eq!(huge.delay(1_000), Duration::from_secs(60));
+}
+
+#[test]
+fn succeeds_after_failures() {
+    let clock = FakeClock::new();
+    let retry = Retry::new().max_attempts(5).backoff(Backoff::Exponential {
+        initial: ms(100),
+        factor: 2,
+        max: ms(1_000),
+    });
+
+    let report = retry.run(&clock, |attempt| {
+        // 每次尝试本身花 10ms。
+        clock.advance(ms(10));
+        if attempt < 3 {
+            Err(format!("attempt {} failed", attempt))
+        } else {
+            Ok(attempt * 10)
+        }
+    });
+
+    assert_eq!(report.result, Ok(30));
+    assert_eq!(report.attempts, 3);
+    assert_eq!(report.delays, vec![ms(100), ms(200)]);
+    assert_eq!(clock.sleeps(), report.delays);
+    assert_eq!(report.elapsed, ms(330));
+}
+
+#[test]
+fn exhausted_keeps_last_failure() {
+    let clock = FakeClock::new();
+    let report = Retry::new()
+        .max_attempts(4)
+        .backoff(Backoff::Fixed(ms(50)))
+        .run(&clock, Err::<(), u32>);
+
+    assert_eq!(report.result, Err(Stopped::Exhausted(4)));
+    assert_eq!(report.attempts, 4);
+    // 最后一次失败之后不再等待。
+    assert_eq!(report.delays, vec![ms(50); 3]);
+    assert_eq!(report.elapsed, ms(150));
+
+    let once = Retry::new().max_attempts(0).run(&clock, |_| None::<()>);
+    assert_eq!(
+        (once.attempts, once.result),
+        (1, Err(Stopped::Exhausted(())))
+    );
+}
+
+#[test]
+fn poll_until_timeout() {
+    let clock = FakeClock::new();
+    let report = Retry::poll(ms(300))
+        .timeout(ms(1_000))
+        .run(&clock, |_| None::<i32>);
+
+    // 0, 300, 600, 900 各试一次；再等 300ms 就超过 1s 了。
+    assert_eq!(report.attempts, 4);
+    assert_eq!(report.result, Err(Stopped::TimedOut(())));
+    assert_eq!(report.elapsed, ms(900));
+
+    // 轮询一个不断变化的值，就像 `z_while_let` 里的 `optional`。
+    let mut i = 0;
+    let report = Retry::poll(ms(1)).run(&clock, |_| {
+        i += 1;
+        if i > 9 {
+            Some(i)
+        } else {
+            None
+        }
+    });
+    assert_eq!((report.result, report.attempts), (Ok(10), 10));
+}
+
+#[test]
+fn cancellation() {
+    let clock = FakeClock::new();
+    let token = CancelToken::new();
+    let retry = Retry::new()
+        .unlimited()
+        .backoff(Backoff::Fixed(ms(10)))
+        .cancel_token(token.clone());
+
+    let report = retry.run(&clock, |attempt| {
+        if attempt == 3 {
+            token.cancel();
+        }
+        Err::<(), _>(attempt)
+    });
+    assert_eq!(report.result, Err(Stopped::Cancelled(Some(3))));
+    assert_eq!(report.attempts, 3);
+    assert_eq!(report.delays.len(), 2);
+
+    // 已经取消的令牌：一次都不尝试。
+    let report = retry.run(&clock, |_| Some(1));
+    assert_eq!(report.result, Err(Stopped::Cancelled(None)));
+    assert_eq!(report.attempts, 0);
+}
+
+#[test]
+fn system_clock_without_delays() {
+    let report = Retry::new()
+        .max_attempts(3)
+        .run(&SystemClock::default(), |attempt| {
+            if attempt == 2 {
+                Some("ok")
+            } else {
+                None
+            }
+        });
+    assert!(report.is_success());
+    assert_eq!(report.delays, vec![Duration::ZERO]);
+}
@@ -0,0 +1,392 @@
+/*!
+ * @Author: ZZX
+ * @Description: 伪随机数
+ * `zt_trait::third` 的 `random_animal` 只能传进一个写死的 `0.234`，因为这个项目没有依赖，
+ * 也就没有随机数。这里实现了两个不依赖第三方库的生成器：xorshift64* 和 PCG32，
+ * 同一个种子总是产生同一个序列。`Rng` 在它们之上提供区间内均匀分布的整数和浮点数、
+ * 洗牌、按权重选择和不放回抽样。
+ * @Date: create in 2026/10/19 5:46 上午
+ */
+
+use std::ops::{Range, RangeInclusive};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 把任意种子（包括 0）打散成分布均匀的 64 位数。
+fn splitmix64(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// 用当前时间做种子，每次运行都不同。
+pub fn seed_from_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0)
+}
+
+pub trait Rng {
+    fn next_u64(&mut self) -> u64;
+
+    fn next_u32(&mut self) -> u32 {
+        // 高位通常比低位质量好。
+        (self.next_u64() >> 32) as u32
+    }
+
+    /// `[0, 1)` 内的均匀分布。
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// `[0, n)` 内的均匀分布，没有取模带来的偏差。
+    ///
+    /// # Panics
+    ///
+    /// `n` 为 0 时 panic。
+    fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "cannot sample below zero");
+        // Lemire 的方法：乘法取高位，只在很少的情况下需要重新抽。
+        let threshold = n.wrapping_neg() % n;
+        loop {
+            let m = self.next_u64() as u128 * n as u128;
+            if m as u64 >= threshold {
+                return (m >> 64) as u64;
+            }
+        }
+    }
+
+    /// 区间内的均匀分布，例如 `rng.range(1..=6)` 或者 `rng.range(0.5..2.0)`。
+    ///
+    /// # Panics
+    ///
+    /// 区间为空时 panic。
+    fn range<T, R: SampleRange<T>>(&mut self, range: R) -> T
+    where
+        Self: Sized,
+    {
+        range.sample(self)
+    }
+
+    /// 以概率 `p` 返回 `true`。
+    fn chance(&mut self, p: f64) -> bool {
+        self.next_f64() < p
+    }
+
+    /// Fisher-Yates 洗牌。
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i as u64 + 1) as usize;
+            items.swap(i, j);
+        }
+    }
+
+    fn choose<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        if items.is_empty() {
+            None
+        } else {
+            Some(&items[self.below(items.len() as u64) as usize])
+        }
+    }
+
+    /// 按权重选一个下标。没有正的权重时返回 `None`。
+    ///
+    /// # Panics
+    ///
+    /// 有负数或者不是有限数的权重时 panic。
+    fn weighted_index(&mut self, weights: &[f64]) -> Option<usize> {
+        assert!(
+            weights.iter().all(|w| w.is_finite() && *w >= 0.0),
+            "weights must be finite and non-negative"
+        );
+        let total: f64 = weights.iter().sum();
+        if total <= 0.0 {
+            return None;
+        }
+        let mut target = self.next_f64() * total;
+        for (i, &w) in weights.iter().enumerate() {
+            if target < w {
+                return Some(i);
+            }
+            target -= w;
+        }
+        // 浮点误差可能让 `target` 剩下一点，落到最后一个正权重上。
+        weights.iter().rposition(|&w| w > 0.0)
+    }
+
+    fn choose_weighted<'a, T, F>(&mut self, items: &'a [T], weight: F) -> Option<&'a T>
+    where
+        F: FnMut(&T) -> f64,
+    {
+        let weights: Vec<f64> = items.iter().map(weight).collect();
+        self.weighted_index(&weights).map(|i| &items[i])
+    }
+
+    /// 不放回地抽 `k` 个，`k` 超过总数时全部抽出，顺序是随机的。
+    fn sample<'a, T>(&mut self, items: &'a [T], k: usize) -> Vec<&'a T> {
+        let mut indices: Vec<usize> = (0..items.len()).collect();
+        let k = k.min(items.len());
+        // 只洗前 `k` 个位置。
+        for i in 0..k {
+            let j = i + self.below((indices.len() - i) as u64) as usize;
+            indices.swap(i, j);
+        }
+        indices[..k].iter().map(|&i| &items[i]).collect()
+    }
+}
+
+/// `Rng::range` 接受的区间。
+pub trait SampleRange<T> {
+    fn sample<G: Rng + ?Sized>(self, rng: &mut G) -> T;
+}
+
+macro_rules! impl_sample_range {
+    ($($t:ty),*) => {$(
+        impl SampleRange<$t> for Range<$t> {
+            fn sample<G: Rng + ?Sized>(self, rng: &mut G) -> $t {
+                assert!(self.start < self.end, "cannot sample empty range");
+                let span = (self.end as i128 - self.start as i128) as u64;
+                (self.start as i128 + rng.below(span) as i128) as $t
+            }
+        }
+
+        impl SampleRange<$t> for RangeInclusive<$t> {
+            fn sample<G: Rng + ?Sized>(self, rng: &mut G) -> $t {
+                let (start, end) = self.into_inner();
+                assert!(start <= end, "cannot sample empty range");
+                let span = (end as i128 - start as i128) as u64;
+                // 整个 64 位的区间没法再加一，直接用原始输出。
+                let offset = match span.checked_add(1) {
+                    Some(n) => rng.below(n),
+                    None => rng.next_u64(),
+                };
+                (start as i128 + offset as i128) as $t
+            }
+        }
+    )*};
+}
+
+impl_sample_range!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl SampleRange<f64> for Range<f64> {
+    fn sample<G: Rng + ?Sized>(self, rng: &mut G) -> f64 {
+        assert!(self.start < self.end, "cannot sample empty range");
+        let x = self.start + rng.next_f64() * (self.end - self.start);
+        // 舍入可能得到 `end`。
+        if x < self.end {
+            x
+        } else {
+            self.start
+        }
+    }
+}
+
+/// xorshift64*：状态只有一个 `u64`，速度快。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XorShift64 {
+    state: u64,
+}
+
+impl XorShift64 {
+    pub fn new(seed: u64) -> Self {
+        // 状态不能是 0，否则会一直输出 0。
+        XorShift64 {
+            state: splitmix64(seed).max(1),
+        }
+    }
+}
+
+impl Rng for XorShift64 {
+    fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        self.state = x;
+        x.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+}
+
+/// PCG32（XSH RR 变体），和 pcg-c-basic 的 `pcg32_srandom_r` 输出相同。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pcg32 {
+    state: u64,
+    increment: u64,
+}
+
+impl Pcg32 {
+    const MULTIPLIER: u64 = 6_364_136_223_846_793_005;
+
+    pub fn new(seed: u64) -> Self {
+        Self::with_stream(seed, 0xDA3E_39CB_94B9_5BDB)
+    }
+
+    /// 同一个种子、不同的 `stream` 产生互不相关的序列。
+    pub fn with_stream(seed: u64, stream: u64) -> Self {
+        let mut rng = Pcg32 {
+            state: 0,
+            increment: (stream << 1) | 1,
+        };
+        rng.step();
+        rng.state = rng.state.wrapping_add(seed);
+        rng.step();
+        rng
+    }
+
+    fn step(&mut self) {
+        self.state = self
+            .state
+            .wrapping_mul(Self::MULTIPLIER)
+            .wrapping_add(self.increment);
+    }
+}
+
+impl Rng for Pcg32 {
+    fn next_u32(&mut self) -> u32 {
+        let old = self.state;
+        self.step();
+        let xorshifted = (((old >> 18) ^ old) >> 27) as u32;
+        let rot = (old >> 59) as u32;
+        xorshifted.rotate_right(rot)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let high = self.next_u32() as u64;
+        (high << 32) | self.next_u32() as u64
+    }
+}
+
+#[cfg(test)]
+fn chi_square(counts: &[u64], expected: f64) -> f64 {
+    counts
+        .iter()
+        .map(|&c| (c as f64 - expected).powi(2) / expected)
+        .sum()
+}
+
+#[test]
+fn reproducible_sequences() {
+    // pcg-c-basic 示例程序 `pcg32-demo` 用种子 42、序列 54 的前几个输出。
+    let mut pcg = Pcg32::with_stream(42, 54);
+    let first: Vec<u32> = (0..6).map(|_| pcg.next_u32()).collect();
+    assert_eq!(
+        first,
+        [0xa15c02b7, 0x7b47f409, 0xba1d3330, 0x83d2f293, 0xbfa4784b, 0xcbed606e]
+    );
+
+    let mut xorshift = XorShift64::new(42);
+    let first: Vec<u64> = (0..3).map(|_| xorshift.next_u64()).collect();
+    assert_eq!(
+        first,
+        [0x31b0ece7c4f697a2, 0x9008a3b1cb686f03, 0x7c7173abd97be16f]
+    );
+
+    // 同一个种子总是得到同样的序列，不同的种子或序列号则不同。
+    let draw = |mut rng: Pcg32| -> Vec<u64> { (0..8).map(|_| rng.range(0..1000)).collect() };
+    assert_eq!(draw(Pcg32::new(7)), draw(Pcg32::new(7)));
+    assert_ne!(draw(Pcg32::new(7)), draw(Pcg32::new(8)));
+    assert_ne!(draw(Pcg32::new(7)), draw(Pcg32::with_stream(7, 1)));
+
+    // 种子 0 也能用。
+    let mut zero = XorShift64::new(0);
+    assert_ne!(zero.next_u64(), zero.next_u64());
+}
+
+#[test]
+fn ranges_are_uniform() {
+    let mut rng = Pcg32::new(2021);
+    let mut counts = [0u64; 10];
+    for _ in 0..100_000 {
+        counts[rng.below(10) as usize] += 1;
+    }
+    // 自由度为 9，显著性 0.001 的临界值是 27.88。
+    assert!(chi_square(&counts, 10_000.0) < 27.88, "{:?}", counts);
+
+    let mut dice = [0u64; 6];
+    for _ in 0..60_000 {
+        let roll: i32 = rng.range(1..=6);
+        dice[roll as usize - 1] += 1;
+    }
+    assert!(chi_square(&dice, 10_000.0) < 20.52, "{:?}", dice);
+
+    let floats: Vec<f64> = (0..100_000).map(|_| rng.range(-1.0..3.0)).collect();
+    let mean = floats.iter().sum::<f64>() / floats.len() as f64;
+    assert!((mean - 1.0).abs() < 0.02, "mean {}", mean);
+    assert!(floats.iter().all(|x| (-1.0..3.0).contains(x)));
+
+    // 边界。
+    let mut xorshift = XorShift64::new(1);
+    for _ in 0..1000 {
+        let x: i8 = xorshift.range(-128..=127);
+        let y: u8 = xorshift.range(250..=255);
+        assert!(y >= 250);
+        let z: i64 = xorshift.range(i64::MIN..=i64::MAX);
+        let _ = (x, z);
+        assert_eq!(xorshift.range(5u32..6), 5);
+    }
+}
+
+#[test]
+fn shuffle_and_sample() {
+    let mut rng = XorShift64::new(99);
+
+    // 每个元素落在每个位置上的次数应该差不多。
+    let mut positions = [[0u64; 4]; 4];
+    for _ in 0..40_000 {
+        let mut items = [0, 1, 2, 3];
+        rng.shuffle(&mut items);
+        for (position, &item) in items.iter().enumerate() {
+            positions[item][position] += 1;
+        }
+    }
+    for row in &positions {
+        assert!(chi_square(row, 10_000.0) < 16.27, "{:?}", positions);
+    }
+
+    let items: Vec<u32> = (0..20).collect();
+    let picked = rng.sample(&items, 5);
+    assert_eq!(picked.len(), 5);
+    let mut distinct = picked.clone();
+    distinct.sort();
+    distinct.dedup();
+    assert_eq!(distinct.len(), 5);
+    assert_eq!(rng.sample(&items, 50).len(), 20);
+    assert!(rng.sample::<u32>(&[], 3).is_empty());
+
+    assert!(rng.choose::<u32>(&[]).is_none());
+    assert!(items.contains(rng.choose(&items).unwrap()));
+}
+
+#[test]
+fn weighted_choice() {
+    let mut rng = Pcg32::new(5);
+    let weights = [1.0, 0.0, 3.0, 6.0];
+    let mut counts = [0u64; 4];
+    for _ in 0..100_000 {
+        counts[rng.weighted_index(&weights).unwrap()] += 1;
+    }
+    assert_eq!(counts[1], 0);
+    let observed: Vec<f64> = counts.iter().map(|&c| c as f64 / 100_000.0).collect();
+    for (p, w) in observed.iter().zip(&weights) {
+        assert!((p - w / 10.0).abs() < 0.01, "{:?}", observed);
+    }
+
+    assert_eq!(rng.weighted_index(&[]), None);
+    assert_eq!(rng.weighted_index(&[0.0, 0.0]), None);
+
+    let animals = [("sheep", 2.0), ("cow", 0.0)];
+    assert_eq!(
+        rng.choose_weighted(&animals, |a| a.1).map(|a| a.0),
+        Some("sheep")
+    );
+
+    let heads = (0..10_000).filter(|_| rng.chance(0.25)).count();
+    assert!((2300..2700).contains(&heads), "{}", heads);
+}
+
+#[test]
+#[should_panic(expected = "weights must be finite and non-negative")]
+fn negative_weights_panic() {
+    Pcg32::new(1).weighted_index(&[1.0, -1.0]);
+}
@@ -0,0 +1,747 @@
+/*!
+ * @Author: ZZX
+ * @Description: 规则引擎
+ * 扩展 `n_use::one`：那里对 `Status` 和 `Work` 分别 match，消息写死在代码里。
+ * 这里的规则从文本文件读入，按枚举取值和整数谓词的组合匹配，
+ * 每条规则向一个输出写入一条消息，同一个输出取第一条匹配的规则。
+ * `RuleSet::analyze` 穷举所有有代表性的输入，找出永远不会生效的规则
+ * 以及只靠先后顺序解决的冲突。
+ * @Date: create in 2026/10/19 5:46 上午
+ */
+
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+
+use crate::toolkit::enum_reflect::EnumReflect;
+
+/// `analyze` 最多穷举这么多个输入，规则再复杂就放弃分析。
+const MAX_ANALYZED_INPUTS: usize = 1 << 20;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldKind {
+    /// 枚举字段，列出所有变体的名字。
+    Enum(Vec<&'static str>),
+    Int,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Field {
+    pub name: &'static str,
+    pub kind: FieldKind,
+}
+
+/// 规则可以引用的字段。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Schema {
+    fields: Vec<Field>,
+}
+
+impl Schema {
+    pub fn new() -> Self {
+        Schema { fields: Vec::new() }
+    }
+
+    /// 加入一个枚举字段，字段名就是枚举的类型名。
+    pub fn with_enum<E: EnumReflect>(mut self) -> Self {
+        self.fields.push(Field {
+            name: E::NAME,
+            kind: FieldKind::Enum(E::iter().map(E::name).collect()),
+        });
+        self
+    }
+
+    pub fn with_int(mut self, name: &'static str) -> Self {
+        self.fields.push(Field {
+            name,
+            kind: FieldKind::Int,
+        });
+        self
+    }
+
+    pub fn fields(&self) -> &[Field] {
+        &self.fields
+    }
+
+    fn index_of(&self, name: &str) -> Option<usize> {
+        self.fields.iter().position(|f| f.name == name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FactValue {
+    Variant(&'static str),
+    Int(i64),
+}
+
+impl Display for FactValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            FactValue::Variant(name) => write!(f, "{}", name),
+            FactValue::Int(n) => write!(f, "{}", n),
+        }
+    }
+}
+
+/// 一次求值的输入。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Facts {
+    values: Vec<(&'static str, FactValue)>,
+}
+
+impl Facts {
+    pub fn new() -> Self {
+        Facts { values: Vec::new() }
+    }
+
+    pub fn with<E: EnumReflect>(mut self, value: E) -> Self {
+        self.values
+            .push((E::NAME, FactValue::Variant(value.name())));
+        self
+    }
+
+    pub fn with_int(mut self, name: &'static str, value: i64) -> Self {
+        self.values.push((name, FactValue::Int(value)));
+        self
+    }
+
+    fn get(&self, name: &str) -> Option<FactValue> {
+        self.values
+            .iter()
+            .rev()
+            .find(|(n, _)| *n == name)
+            .map(|(_, v)| *v)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Op {
+    fn parse(s: &str) -> Option<Op> {
+        match s {
+            "=" => Some(Op::Eq),
+            "!=" => Some(Op::Ne),
+            "<" => Some(Op::Lt),
+            "<=" => Some(Op::Le),
+            ">" => Some(Op::Gt),
+            ">=" => Some(Op::Ge),
+            _ => None,
+        }
+    }
+
+    fn apply(self, left: i64, right: i64) -> bool {
+        match self {
+            Op::Eq => left == right,
+            Op::Ne => left != right,
+            Op::Lt => left < right,
+            Op::Le => left <= right,
+            Op::Gt => left > right,
+            Op::Ge => left >= right,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Test {
+    /// `Field = A` 或 `Field in A|B`。
+    In(Vec<&'static str>),
+    /// `Field != A`。
+    NotIn(Vec<&'static str>),
+    /// 整数比较，例如 `age >= 18`。
+    Cmp(Op, i64),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Condition {
+    /// 字段在 `Schema` 中的下标。
+    pub field: usize,
+    pub test: Test,
+}
+
+impl Condition {
+    // 缺少这个字段时条件不成立。
+    fn matches(&self, value: Option<FactValue>) -> bool {
+        match (&self.test, value) {
+            (Test::In(names), Some(FactValue::Variant(v))) => names.contains(&v),
+            (Test::NotIn(names), Some(FactValue::Variant(v))) => !names.contains(&v),
+            (Test::Cmp(op, right), Some(FactValue::Int(left))) => op.apply(left, *right),
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+    pub name: String,
+    /// 在规则文件中的行号，从 1 开始。
+    pub line: usize,
+    /// 全部成立时规则匹配；为空表示 `when *`，总是匹配。
+    pub conditions: Vec<Condition>,
+    pub output: String,
+    pub message: String,
+}
+
+impl Rule {
+    fn matches(&self, point: &[Option<FactValue>]) -> bool {
+        self.conditions.iter().all(|c| c.matches(point[c.field]))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleError {
+    /// 出错的行号；为 0 表示读取文件失败。
+    pub line: usize,
+    pub message: String,
+}
+
+impl Display for RuleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.line == 0 {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "line {}: {}", self.line, self.message)
+        }
+    }
+}
+
+/// 一条生效的规则。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Firing<'a> {
+    pub rule: &'a str,
+    pub output: &'a str,
+    pub message: &'a str,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Diagnostic {
+    /// 对任何输入都不会生效。`shadowed_by` 为空表示规则自身的条件互相矛盾。
+    Unreachable {
+        rule: String,
+        shadowed_by: Vec<String>,
+    },
+    /// 两条规则写同一个输出、消息不同、匹配的输入有交集但互不包含，
+    /// 交集上的结果只取决于它们在文件中的顺序。
+    Conflict {
+        first: String,
+        second: String,
+        output: String,
+        example: String,
+    },
+    /// 有代表性的输入超过了 `limit` 个，没有做分析。
+    TooComplex { limit: usize },
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Diagnostic::Unreachable { rule, shadowed_by } if shadowed_by.is_empty() => {
+                write!(f, "rule `{}` can never match", rule)
+            }
+            Diagnostic::Unreachable { rule, shadowed_by } => write!(
+                f,
+                "rule `{}` is unreachable, shadowed by `{}`",
+                rule,
+                shadowed_by.join("`, `")
+            ),
+            Diagnostic::Conflict {
+                first,
+                second,
+                output,
+                example,
+            } => write!(
+                f,
+                "rules `{}` and `{}` both set `{}` for {}",
+                first, second, output, example
+            ),
+            Diagnostic::TooComplex { limit } => write!(
+                f,
+                "the rules need more than {} inputs to analyze, skipped",
+                limit
+            ),
+        }
+    }
+}
+
+pub struct RuleSet {
+    schema: Schema,
+    rules: Vec<Rule>,
+}
+
+impl RuleSet {
+    /// 解析规则文件的内容：每行一条规则，`#` 开头的行是注释。
+    ///
+    /// ```text
+    /// adult  when Status = Rich and age >= 18  then greeting = "Welcome!"
+    /// ```
+    pub fn parse(schema: Schema, text: &str) -> Result<RuleSet, RuleError> {
+        let mut rules: Vec<Rule> = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            let error = |message: String| RuleError {
+                line: i + 1,
+                message,
+            };
+            let rule = parse_rule(&schema, trimmed, i + 1).map_err(error)?;
+            if rules.iter().any(|r| r.name == rule.name) {
+                return Err(error(format!("duplicate rule name `{}`", rule.name)));
+            }
+            rules.push(rule);
+        }
+        Ok(RuleSet { schema, rules })
+    }
+
+    pub fn load(schema: Schema, path: &str) -> Result<RuleSet, RuleError> {
+        let text = fs::read_to_string(path).map_err(|e| RuleError {
+            line: 0,
+            message: format!("{}: {}", path, e),
+        })?;
+        RuleSet::parse(schema, &text)
+    }
+
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    /// 对每个输出取第一条匹配的规则，按规则在文件中的顺序返回。
+    pub fn evaluate(&self, facts: &Facts) -> Vec<Firing<'_>> {
+        let point: Vec<Option<FactValue>> = self
+            .schema
+            .fields
+            .iter()
+            .map(|f| facts.get(f.name))
+            .collect();
+
+        let mut firings: Vec<Firing<'_>> = Vec::new();
+        for rule in &self.rules {
+            if firings.iter().any(|f| f.output == rule.output) || !rule.matches(&point) {
+                continue;
+            }
+            firings.push(Firing {
+                rule: &rule.name,
+                output: &rule.output,
+                message: &rule.message,
+            });
+        }
+        firings
+    }
+
+    /// 找出不可达的规则和冲突。
+    pub fn analyze(&self) -> Vec<Diagnostic> {
+        let values = self.field_values();
+        let count = values
+            .iter()
+            .try_fold(1usize, |n, v| n.checked_mul(v.len()));
+        if count.is_none_or(|n| n > MAX_ANALYZED_INPUTS) {
+            return vec![Diagnostic::TooComplex {
+                limit: MAX_ANALYZED_INPUTS,
+            }];
+        }
+        let points = points(&values);
+        let matches: Vec<Vec<bool>> = self
+            .rules
+            .iter()
+            .map(|r| points.iter().map(|p| r.matches(p)).collect())
+            .collect();
+
+        let mut diagnostics = Vec::new();
+        for (j, rule) in self.rules.iter().enumerate() {
+            let earlier: Vec<usize> = (0..j)
+                .filter(|&i| self.rules[i].output == rule.output)
+                .collect();
+            let fires =
+                (0..points.len()).any(|p| matches[j][p] && !earlier.iter().any(|&i| matches[i][p]));
+
+            if !fires {
+                // 只列出在这条规则能匹配的输入上真正生效的那些规则。
+                let mut winners: Vec<usize> = (0..points.len())
+                    .filter(|&p| matches[j][p])
+                    .filter_map(|p| earlier.iter().copied().find(|&i| matches[i][p]))
+                    .collect();
+                winners.sort_unstable();
+                winners.dedup();
+                let shadowed_by = winners
+                    .into_iter()
+                    .map(|i| self.rules[i].name.clone())
+                    .collect();
+                diagnostics.push(Diagnostic::Unreachable {
+                    rule: rule.name.clone(),
+                    shadowed_by,
+                });
+                continue;
+            }
+
+            for &i in &earlier {
+                if self.rules[i].message == rule.message {
+                    continue;
+                }
+                let overlap = (0..points.len()).find(|&p| matches[i][p] && matches[j][p]);
+                // 先写具体规则、后写一般规则是正常用法，不算冲突。
+                let first_is_narrower = (0..points.len()).all(|p| !matches[i][p] || matches[j][p]);
+                if let (Some(p), false) = (overlap, first_is_narrower) {
+                    diagnostics.push(Diagnostic::Conflict {
+                        first: self.rules[i].name.clone(),
+                        second: rule.name.clone(),
+                        output: rule.output.clone(),
+                        example: self.describe(&points[p]),
+                    });
+                }
+            }
+        }
+        diagnostics
+    }
+
+    // 每个字段有代表性的值。整数字段取规则中出现的每个常数及其前后的值，
+    // 这样每个比较条件划分出的每个区间都至少有一个代表。
+    // 最后加上 `None`：`evaluate` 允许缺少字段，分析时也要考虑。
+    fn field_values(&self) -> Vec<Vec<Option<FactValue>>> {
+        self.schema
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| {
+                let values: Vec<FactValue> = match &field.kind {
+                    FieldKind::Enum(names) => names.iter().map(|n| FactValue::Variant(n)).collect(),
+                    FieldKind::Int => {
+                        let mut ints = vec![0];
+                        for c in self.rules.iter().flat_map(|r| &r.conditions) {
+                            if let (true, Test::Cmp(_, n)) = (c.field == index, &c.test) {
+                                ints.extend([n.saturating_sub(1), *n, n.saturating_add(1)]);
+                            }
+                        }
+                        ints.sort_unstable();
+                        ints.dedup();
+                        ints.into_iter().map(FactValue::Int).collect()
+                    }
+                };
+                values.into_iter().map(Some).chain([None]).collect()
+            })
+            .collect()
+    }
+
+    fn describe(&self, point: &[Option<FactValue>]) -> String {
+        self.schema
+            .fields
+            .iter()
+            .zip(point)
+            .filter_map(|(f, v)| v.map(|v| format!("{}={}", f.name, v)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+// 各个字段取值的笛卡尔积。
+fn points(values: &[Vec<Option<FactValue>>]) -> Vec<Vec<Option<FactValue>>> {
+    let mut points: Vec<Vec<Option<FactValue>>> = vec![Vec::new()];
+    for field in values {
+        points = points
+            .into_iter()
+            .flat_map(|p| {
+                field.iter().map(move |v| {
+                    let mut next = p.clone();
+                    next.push(*v);
+                    next
+                })
+            })
+            .collect();
+    }
+    points
+}
+
+fn parse_rule(schema: &Schema, line: &str, line_no: usize) -> Result<Rule, String> {
+    let (head, action) = line
+        .split_once(" then ")
+        .ok_or_else(|| "expected `then`".to_string())?;
+    let (name, conditions) = head
+        .trim()
+        .split_once(" when ")
+        .ok_or_else(|| "expected `<name> when <conditions>`".to_string())?;
+    let name = name.trim();
+    if name.is_empty() || name.contains(char::is_whitespace) {
+        return Err(format!("invalid rule name `{}`", name));
+    }
+
+    let conditions = match conditions.trim() {
+        "*" => Vec::new(),
+        text => text
+            .split(" and ")
+            .map(|c| parse_condition(schema, c))
+            .collect::<Result<Vec<_>, _>>()?,
+    };
+
+    let (output, message) = action
+        .split_once('=')
+        .ok_or_else(|| "expected `<output> = \"<message>\"`".to_string())?;
+    let output = output.trim();
+    let message = message.trim();
+    if output.is_empty() || output.contains(char::is_whitespace) {
+        return Err(format!("invalid output name `{}`", output));
+    }
+    let message = message
+        .strip_prefix('"')
+        .and_then(|m| m.strip_suffix('"'))
+        .ok_or_else(|| format!("message {} must be in double quotes", message))?;
+
+    Ok(Rule {
+        name: name.to_string(),
+        line: line_no,
+        conditions,
+        output: output.to_string(),
+        message: message.to_string(),
+    })
+}
+
+fn parse_condition(schema: &Schema, text: &str) -> Result<Condition, String> {
+    let tokens: Vec<&str> = text.split_whitespace().collect();
+    let (name, op, value) = match tokens[..] {
+        [name, op, value] => (name, op, value),
+        _ => {
+            return Err(format!(
+                "expected `<field> <op> <value>`, found `{}`",
+                text.trim()
+            ))
+        }
+    };
+    let field = schema
+        .index_of(name)
+        .ok_or_else(|| format!("unknown field `{}`", name))?;
+
+    let test = match &schema.fields[field].kind {
+        FieldKind::Enum(variants) => {
+            let names = value
+                .split('|')
+                .map(|v| {
+                    variants
+                        .iter()
+                        .find(|known| **known == v)
+                        .copied()
+                        .ok_or_else(|| format!("`{}` is not a variant of {}", v, name))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            match op {
+                "=" | "in" => Test::In(names),
+                "!=" => Test::NotIn(names),
+                _ => return Err(format!("operator `{}` cannot be used with {}", op, name)),
+            }
+        }
+        FieldKind::Int => {
+            let op = Op::parse(op)
+                .ok_or_else(|| format!("operator `{}` cannot be used with {}", op, name))?;
+            let n = value
+                .parse()
+                .map_err(|_| format!("`{}` is not an integer", value))?;
+            Test::Cmp(op, n)
+        }
+    };
+    Ok(Condition { field, test })
+}
+
+#[cfg(test)]
+fn n_use_schema() -> Schema {
+    use crate::example::n_use::{Status, Work};
+    Schema::new().with_enum::<Status>().with_enum::<Work>()
+}
+
+#[test]
+fn n_use_scenario() {
+    use crate::example::n_use::{Status, Work};
+
+    let rules = RuleSet::parse(n_use_schema(), include_str!("../example/n_use.rules")).unwrap();
+    assert_eq!(rules.rules().len(), 4);
+    assert_eq!(rules.analyze(), vec![]);
+
+    let facts = Facts::new().with(Status::Poor).with(Work::Civilian);
+    let messages: Vec<&str> = rules.evaluate(&facts).iter().map(|f| f.message).collect();
+    assert_eq!(
+        messages,
+        vec!["The poor have no money...", "Civilians work!"]
+    );
+
+    // 每种组合都恰好得到两条消息。
+    for status in Status::iter() {
+        for work in Work::iter() {
+            let firings = rules.evaluate(&Facts::new().with(status).with(work));
+            let outputs: Vec<&str> = firings.iter().map(|f| f.output).collect();
+            assert_eq!(outputs, vec!["status", "work"]);
+        }
+    }
+}
+
+#[test]
+fn predicates_and_first_match() {
+    use crate::example::n_use::Status;
+
+    let text = "
+        # 整数谓词和枚举条件可以组合
+        minor     when age < 18                      then greeting = \"Hi kid\"
+        rich_vip  when Status = Rich and age >= 65   then greeting = \"Welcome back, sir\"
+        adult     when age >= 18                     then greeting = \"Hello\"
+        anyone    when *                             then footer = \"Bye\"
+    ";
+    let rules = RuleSet::parse(n_use_schema().with_int("age"), text).unwrap();
+
+    let greet = |status: Status, age: i64| {
+        let facts = Facts::new().with(status).with_int("age", age);
+        rules
+            .evaluate(&facts)
+            .iter()
+            .map(|f| f.message.to_string())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(greet(Status::Rich, 10), vec!["Hi kid", "Bye"]);
+    assert_eq!(greet(Status::Rich, 70), vec!["Welcome back, sir", "Bye"]);
+    assert_eq!(greet(Status::Poor, 70), vec!["Hello", "Bye"]);
+
+    // 缺少字段时相关的条件不成立。
+    let firings = rules.evaluate(&Facts::new());
+    assert_eq!(firings.len(), 1);
+    assert_eq!(firings[0].rule, "anyone");
+    assert_eq!(rules.analyze(), vec![]);
+}
+
+#[test]
+fn unreachable_rules() {
+    let text = "
+        any_rich    when Status = Rich                        then status = \"rich\"
+        rich_civ    when Status = Rich and Work = Civilian    then status = \"rich civilian\"
+        poor        when Status = Poor                        then status = \"poor\"
+        fallback    when Status in Rich|Poor                  then status = \"?\"
+        impossible  when age > 10 and age < 5                 then other = \"never\"
+    ";
+    let rules = RuleSet::parse(n_use_schema().with_int("age"), text).unwrap();
+    let diagnostics = rules.analyze();
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            Diagnostic::Unreachable {
+                rule: "rich_civ".to_string(),
+                shadowed_by: vec!["any_rich".to_string()],
+            },
+            Diagnostic::Unreachable {
+                rule: "fallback".to_string(),
+                shadowed_by: vec!["any_rich".to_string(), "poor".to_string()],
+            },
+            Diagnostic::Unreachable {
+                rule: "impossible".to_string(),
+                shadowed_by: vec![],
+            },
+        ]
+    );
+    assert_eq!(
+        diagnostics[1].to_string(),
+        "rule `fallback` is unreachable, shadowed by `any_rich`, `poor`"
+    );
+    assert_eq!(
+        diagnostics[2].to_string(),
+        "rule `impossible` can never match"
+    );
+}
+
+#[test]
+fn conflicts() {
+    let text = "
+        rich     when Status = Rich     then mood = \"happy\"
+        soldier  when Work = Soldier    then mood = \"tired\"
+        default  when *                 then mood = \"meh\"
+    ";
+    let rules = RuleSet::parse(n_use_schema(), text).unwrap();
+    let diagnostics = rules.analyze();
+
+    // `default` 比前两条都宽，不算冲突；`rich` 和 `soldier` 只是部分重叠。
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].to_string(),
+        "rules `rich` and `soldier` both set `mood` for Status=Rich, Work=Soldier"
+    );
+}
+
+#[test]
+fn parse_errors() {
+    let parse = |text: &str| {
+        RuleSet::parse(n_use_schema().with_int("age"), text)
+            .err()
+            .map(|e| e.to_string())
+    };
+
+    assert_eq!(
+        parse("a when Status = Broke then x = \"y\""),
+        Some("line 1: `Broke` is not a variant of Status".to_string())
+    );
+    assert_eq!(
+        parse("\na when Mood = Rich then x = \"y\""),
+        Some("line 2: unknown field `Mood`".to_string())
+    );
+    assert_eq!(
+        parse("a when Status < Rich then x = \"y\""),
+        Some("line 1: operator `<` cannot be used with Status".to_string())
+    );
+    assert_eq!(
+        parse("a when age >= old then x = \"y\""),
+        Some("line 1: `old` is not an integer".to_string())
+    );
+    assert_eq!(
+        parse("a when * then x = y"),
+        Some("line 1: message y must be in double quotes".to_string())
+    );
+    assert_eq!(
+        parse("a when *\nb when * then x = \"y\""),
+        Some("line 1: expected `then`".to_string())
+    );
+    assert_eq!(
+        parse("a when * then x = \"1\"\na when * then x = \"2\""),
+        Some("line 2: duplicate rule name `a`".to_string())
+    );
+    assert!(RuleSet::load(n_use_schema(), "/nonexistent.rules").is_err());
+}
+
+#[test]
+fn missing_fields_and_limits() {
+    let text = "
+        known    when Status in Rich|Poor    then status = \"known\"
+        unknown  when *                      then status = \"unknown\"
+    ";
+    let rules = RuleSet::parse(n_use_schema(), text).unwrap();
+    // 没有 `Status` 时 `unknown` 会生效，所以它不是不可达的。
+    assert_eq!(rules.analyze(), vec![]);
+    assert_eq!(rules.evaluate(&Facts::new())[0].rule, "unknown");
+
+    // 五个整数字段，每个有二十个常数，组合起来太多了。
+    let mut schema = Schema::new();
+    let mut text = String::new();
+    for field in ["a", "b", "c", "d", "e"] {
+        schema = schema.with_int(field);
+        for n in 0..20 {
+            let line = format!(
+                "{}{} when {} > {} then {} = \"x\"\n",
+                field,
+                n,
+                field,
+                n * 10,
+                field
+            );
+            text.push_str(&line);
+        }
+    }
+    let rules = RuleSet::parse(schema, &text).unwrap();
+    let diagnostics = rules.analyze();
+    assert_eq!(
+        diagnostics,
+        vec![Diagnostic::TooComplex {
+            limit: MAX_ANALYZED_INPUTS
+        }]
+    );
+    assert_eq!(
+        diagnostics[0].to_string(),
+        "the rules need more than 1048576 inputs to analyze, skipped"
+    );
+}
@@ -0,0 +1,301 @@
+/*!
+ * @Author: ZZX
+ * @Description: 作用域守卫
+ * `zt_trait::sixth` 的 `Droppable` 和 `zr_scoping_rules::one` 的 `ToDrop` 只是在 `drop` 里打印，
+ * 让人看出值是按什么顺序销毁的。`ScopeGuard` 把任意闭包挂到 `drop` 上：`defer!` 在离开作用域时执行，
+ * `guard_on_success` 只在正常离开时执行，`guard_on_unwind` 只在 panic 展开时执行，
+ * `dismiss` 可以取消守卫。`DropLog` 记录销毁顺序，测试可以直接断言而不用去读标准输出。
+ * @Date: create in 2026/10/19 5:46 上午
+ */
+
+use std::cell::RefCell;
+use std::fmt::{self, Debug, Formatter};
+use std::ops::{Deref, DerefMut};
+use std::rc::Rc;
+use std::thread;
+
+/// 守卫在什么情况下执行。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    Always,
+    /// 正常离开作用域时。
+    OnSuccess,
+    /// panic 展开时。
+    OnUnwind,
+}
+
+impl Strategy {
+    fn should_run(self) -> bool {
+        match self {
+            Strategy::Always => true,
+            Strategy::OnSuccess => !thread::panicking(),
+            Strategy::OnUnwind => thread::panicking(),
+        }
+    }
+}
+
+/// 拥有一个值，销毁时按 `strategy` 决定是否把值交给 `action`。
+/// 守卫可以像 `Box` 一样解引用到这个值。
+pub struct ScopeGuard<T, F: FnOnce(T)> {
+    /// 只有 `dismiss` 和 `drop` 会把它变成 `None`，两者都消耗了守卫。
+    inner: Option<(T, F)>,
+    strategy: Strategy,
+}
+
+impl<T, F: FnOnce(T)> ScopeGuard<T, F> {
+    pub fn with_strategy(value: T, strategy: Strategy, action: F) -> Self {
+        ScopeGuard {
+            inner: Some((value, action)),
+            strategy,
+        }
+    }
+
+    pub fn strategy(&self) -> Strategy {
+        self.strategy
+    }
+
+    /// 取消守卫，拿回值，`action` 不会执行。
+    pub fn dismiss(mut self) -> T {
+        let (value, _action) = self.inner.take().expect("guard is armed");
+        value
+    }
+}
+
+impl<T, F: FnOnce(T)> Deref for ScopeGuard<T, F> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.inner.as_ref().expect("guard is armed").0
+    }
+}
+
+impl<T, F: FnOnce(T)> DerefMut for ScopeGuard<T, F> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.inner.as_mut().expect("guard is armed").0
+    }
+}
+
+impl<T, F: FnOnce(T)> Drop for ScopeGuard<T, F> {
+    fn drop(&mut self) {
+        if let Some((value, action)) = self.inner.take() {
+            if self.strategy.should_run() {
+                action(value);
+            }
+        }
+    }
+}
+
+impl<T: Debug, F: FnOnce(T)> Debug for ScopeGuard<T, F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScopeGuard")
+            .field("value", &**self)
+            .field("strategy", &self.strategy)
+            .finish()
+    }
+}
+
+/// 离开作用域时总是执行。
+pub fn guard<T, F: FnOnce(T)>(value: T, action: F) -> ScopeGuard<T, F> {
+    ScopeGuard::with_strategy(value, Strategy::Always, action)
+}
+
+/// 只在正常离开作用域时执行，适合提交之类的收尾工作。
+pub fn guard_on_success<T, F: FnOnce(T)>(value: T, action: F) -> ScopeGuard<T, F> {
+    ScopeGuard::with_strategy(value, Strategy::OnSuccess, action)
+}
+
+/// 只在 panic 展开时执行，适合回滚之类的补救工作。
+pub fn guard_on_unwind<T, F: FnOnce(T)>(value: T, action: F) -> ScopeGuard<T, F> {
+    ScopeGuard::with_strategy(value, Strategy::OnUnwind, action)
+}
+
+/// 不带值的守卫，`defer!` 用的就是它。
+pub fn scope_exit<F: FnOnce()>(action: F) -> ScopeGuard<(), impl FnOnce(())> {
+    guard((), move |()| action())
+}
+
+/// 在当前作用域结束时执行代码块，多个 `defer!` 按声明的相反顺序执行：
+///
+/// ```ignore
+/// defer! { println!("second"); }
+/// defer! { println!("first"); }
+/// ```
+///
+/// 守卫绑定在宏内部的变量上，所以没法 `dismiss`；需要取消时直接用 `scope_exit`。
+#[allow(unused_macros)]
+macro_rules! defer {
+    ($($body:tt)*) => {
+        let _guard = $crate::toolkit::scope_guard::scope_exit(|| { $($body)* });
+    };
+}
+
+#[allow(unused_imports)]
+pub(crate) use defer;
+
+/// 记录销毁顺序的日志。克隆出来的句柄共享同一份记录。
+#[derive(Debug, Clone, Default)]
+pub struct DropLog {
+    events: Rc<RefCell<Vec<String>>>,
+}
+
+impl DropLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, event: impl Into<String>) {
+        self.events.borrow_mut().push(event.into());
+    }
+
+    /// 一个销毁时把 `name` 记进日志的值，相当于会记账的 `Droppable`。
+    pub fn track(&self, name: impl Into<String>) -> Tracked {
+        Tracked {
+            name: name.into(),
+            log: self.clone(),
+        }
+    }
+
+    pub fn events(&self) -> Vec<String> {
+        self.events.borrow().clone()
+    }
+
+    /// 取出目前的记录并清空日志。
+    pub fn take(&self) -> Vec<String> {
+        self.events.borrow_mut().drain(..).collect()
+    }
+}
+
+#[derive(Debug)]
+pub struct Tracked {
+    name: String,
+    log: DropLog,
+}
+
+impl Tracked {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        self.log.record(self.name.clone());
+    }
+}
+
+#[cfg(test)]
+use std::panic::{self, AssertUnwindSafe};
+
+#[test]
+fn tracked_values_drop_in_reverse_order() {
+    // 和 `zt_trait::sixth` 的代码块一样，只是把打印换成了记录。
+    let log = DropLog::new();
+    {
+        let _a = log.track("a");
+        {
+            let _b = log.track("b");
+            {
+                let _c = log.track("c");
+                let _d = log.track("d");
+                log.record("exiting block B");
+            }
+            log.record("exiting block A");
+        }
+        let moved = log.track("e");
+        std::mem::drop(moved);
+        log.record("end");
+    }
+    assert_eq!(
+        log.take(),
+        [
+            "exiting block B",
+            "d",
+            "c",
+            "exiting block A",
+            "b",
+            "e",
+            "end",
+            "a"
+        ]
+    );
+    assert!(log.events().is_empty());
+}
+
+#[test]
+fn defer_runs_in_reverse_order() {
+    let log = DropLog::new();
+    {
+        defer! { log.record("first deferred"); }
+        defer! { log.record("second deferred"); }
+        let _value = log.track("value");
+        log.record("body");
+    }
+    assert_eq!(
+        log.events(),
+        ["body", "value", "second deferred", "first deferred"]
+    );
+}
+
+#[test]
+fn guards_own_and_expose_their_value() {
+    let log = DropLog::new();
+    {
+        let mut buffer = guard(Vec::new(), |v: Vec<i32>| {
+            log.record(format!("flushing {:?}", v));
+        });
+        buffer.push(1);
+        buffer.push(2);
+        assert_eq!(buffer.len(), 2);
+        assert_eq!(
+            format!("{:?}", buffer),
+            "ScopeGuard { value: [1, 2], strategy: Always }"
+        );
+    }
+    assert_eq!(log.take(), ["flushing [1, 2]"]);
+
+    // 取消之后拿回值，`action` 不会执行。
+    let armed = guard(String::from("kept"), |_| log.record("never"));
+    assert_eq!(armed.dismiss(), "kept");
+    let cancelled = scope_exit(|| log.record("never"));
+    cancelled.dismiss();
+    assert!(log.events().is_empty());
+}
+
+#[test]
+fn success_and_unwind_guards() {
+    fn work(log: &DropLog, fail: bool) {
+        let _commit = guard_on_success((), |()| log.record("commit"));
+        let _rollback = guard_on_unwind((), |()| log.record("rollback"));
+        defer! { log.record("cleanup"); }
+        if fail {
+            panic!("work failed");
+        }
+    }
+
+    let log = DropLog::new();
+    work(&log, false);
+    assert_eq!(log.take(), ["cleanup", "commit"]);
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| work(&log, true)));
+    assert!(result.is_err());
+    assert_eq!(log.take(), ["cleanup", "rollback"]);
+}
+
+#[test]
+fn values_are_dropped_on_unwind_too() {
+    let log = DropLog::new();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let _outer = log.track("outer");
+        let _guarded = guard(log.track("guarded"), |t| {
+            log.record(format!("releasing {}", t.name()));
+        });
+        let _inner = log.track("inner");
+        panic!("boom");
+    }));
+    assert!(result.is_err());
+    // `action` 拿到值之后值才被销毁。
+    assert_eq!(
+        log.events(),
+        ["inner", "releasing guarded", "guarded", "outer"]
+    );
+}
@@ -0,0 +1,369 @@
+/*!
+ * @Author: ZZX
+ * @Description: 切片统计
+ * 扩展 `k_array_and_slice::one` 里的 `analyze_slice`：那里只打印 `slice[0]`
+ * 和 `len()`，遇到空切片会 panic。这里为任意数值切片提供最小值、最大值、
+ * 和、平均数、中位数、众数、方差、百分位数和直方图，
+ * 空切片返回 `None` 或 `Err`，不会 panic。
+ * @Date: create in 2026/10/19 5:46 上午
+ */
+
+use std::cmp::Ordering;
+use std::fmt::{self, Display, Formatter};
+
+/// 可以参与统计的数值类型。
+///
+/// 比较和求和都在原类型里进行，只有平均数、方差和百分位数才转换成 `f64`，
+/// 所以超过 2^53 的整数也不会被当成相等。
+pub trait Numeric: Copy {
+    const ZERO: Self;
+
+    fn to_f64(self) -> f64;
+    /// 全序比较，浮点数用 `total_cmp`，NaN 也有确定的位置。
+    fn total_order(&self, other: &Self) -> Ordering;
+    /// 整数溢出时返回 `None`，浮点数照常相加。
+    fn checked_plus(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_numeric {
+    (int: $($t:ty),*) => {$(
+        impl Numeric for $t {
+            const ZERO: Self = 0;
+
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+
+            fn total_order(&self, other: &Self) -> Ordering {
+                self.cmp(other)
+            }
+
+            fn checked_plus(self, other: Self) -> Option<Self> {
+                self.checked_add(other)
+            }
+        }
+    )*};
+    (float: $($t:ty),*) => {$(
+        impl Numeric for $t {
+            const ZERO: Self = 0.0;
+
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+
+            fn total_order(&self, other: &Self) -> Ordering {
+                self.total_cmp(other)
+            }
+
+            fn checked_plus(self, other: Self) -> Option<Self> {
+                Some(self + other)
+            }
+        }
+    )*};
+}
+
+impl_numeric!(int: i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_numeric!(float: f32, f64);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StatsError {
+    Empty,
+    /// 百分位数必须在 `[0, 100]` 之内。
+    InvalidPercentile(f64),
+    ZeroBins,
+}
+
+impl Display for StatsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            StatsError::Empty => write!(f, "the slice is empty"),
+            StatsError::InvalidPercentile(p) => {
+                write!(f, "percentile {} is outside of [0, 100]", p)
+            }
+            StatsError::ZeroBins => write!(f, "a histogram needs at least one bin"),
+        }
+    }
+}
+
+// 全序比较，排序永远不会 panic。
+fn compare<T: Numeric>(a: &T, b: &T) -> Ordering {
+    a.total_order(b)
+}
+
+fn sorted<T: Numeric>(xs: &[T]) -> Vec<T> {
+    let mut v = xs.to_vec();
+    v.sort_by(compare);
+    v
+}
+
+/// 等宽直方图，覆盖 `[lo, hi]`，最后一个桶包含右端点。
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    pub lo: f64,
+    pub hi: f64,
+    pub counts: Vec<usize>,
+}
+
+impl Histogram {
+    pub fn bin_width(&self) -> f64 {
+        (self.hi - self.lo) / self.counts.len() as f64
+    }
+
+    /// 第 `i` 个桶的范围 `[start, end)`。
+    pub fn bin_range(&self, i: usize) -> (f64, f64) {
+        let w = self.bin_width();
+        (self.lo + w * i as f64, self.lo + w * (i + 1) as f64)
+    }
+}
+
+impl Display for Histogram {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (i, count) in self.counts.iter().enumerate() {
+            let (start, end) = self.bin_range(i);
+            writeln!(f, "[{:>8.2}, {:>8.2}) {}", start, end, "*".repeat(*count))?;
+        }
+        Ok(())
+    }
+}
+
+/// 一次性计算出的常用统计量。
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary<T> {
+    pub len: usize,
+    pub min: T,
+    pub max: T,
+    pub sum: f64,
+    pub mean: f64,
+    pub median: f64,
+    pub variance: f64,
+}
+
+/// 切片的统计方法。
+pub trait SliceStats<T: Numeric> {
+    fn min_value(&self) -> Option<T>;
+    fn max_value(&self) -> Option<T>;
+    /// 在原类型里求和，整数溢出时返回 `None`。空切片的和是 `0`。
+    fn checked_sum(&self) -> Option<T>;
+    /// 转换成 `f64` 再求和，不会溢出，但大整数会丢失精度。空切片的和是 `0`。
+    fn sum_f64(&self) -> f64;
+    fn mean(&self) -> Option<f64>;
+    fn median(&self) -> Option<f64>;
+    /// 出现次数最多的值；次数相同时取较小的那个。
+    fn mode(&self) -> Option<T>;
+    /// 总体方差。
+    fn variance(&self) -> Option<f64>;
+    /// 样本方差，至少需要两个元素。
+    fn sample_variance(&self) -> Option<f64>;
+    fn std_dev(&self) -> Option<f64>;
+    /// 线性插值的百分位数，`p` 在 `[0, 100]` 之内。
+    fn percentile(&self, p: f64) -> Result<f64, StatsError>;
+    fn histogram(&self, bins: usize) -> Result<Histogram, StatsError>;
+    fn summary(&self) -> Result<Summary<T>, StatsError>;
+}
+
+impl<T: Numeric> SliceStats<T> for [T] {
+    fn min_value(&self) -> Option<T> {
+        self.iter().copied().min_by(compare)
+    }
+
+    fn max_value(&self) -> Option<T> {
+        self.iter().copied().max_by(compare)
+    }
+
+    fn checked_sum(&self) -> Option<T> {
+        self.iter().try_fold(T::ZERO, |acc, &x| acc.checked_plus(x))
+    }
+
+    fn sum_f64(&self) -> f64 {
+        self.iter().map(|x| x.to_f64()).sum()
+    }
+
+    fn mean(&self) -> Option<f64> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.sum_f64() / self.len() as f64)
+        }
+    }
+
+    fn median(&self) -> Option<f64> {
+        self.percentile(50.0).ok()
+    }
+
+    fn mode(&self) -> Option<T> {
+        let v = sorted(self);
+        let mut best: Option<(T, usize)> = None;
+
+        // 排好序之后相同的值相邻，数一下每一段的长度即可。
+        let mut i = 0;
+        while i < v.len() {
+            let mut j = i + 1;
+            while j < v.len() && compare(&v[i], &v[j]) == Ordering::Equal {
+                j += 1;
+            }
+            if best.is_none_or(|(_, n)| j - i > n) {
+                best = Some((v[i], j - i));
+            }
+            i = j;
+        }
+        best.map(|(x, _)| x)
+    }
+
+    fn variance(&self) -> Option<f64> {
+        let mean = self.mean()?;
+        let squares: f64 = self.iter().map(|x| (x.to_f64() - mean).powi(2)).sum();
+        Some(squares / self.len() as f64)
+    }
+
+    fn sample_variance(&self) -> Option<f64> {
+        if self.len() < 2 {
+            return None;
+        }
+        self.variance()
+            .map(|v| v * self.len() as f64 / (self.len() - 1) as f64)
+    }
+
+    fn std_dev(&self) -> Option<f64> {
+        self.variance().map(f64::sqrt)
+    }
+
+    fn percentile(&self, p: f64) -> Result<f64, StatsError> {
+        if !(0.0..=100.0).contains(&p) {
+            return Err(StatsError::InvalidPercentile(p));
+        }
+        if self.is_empty() {
+            return Err(StatsError::Empty);
+        }
+
+        let v = sorted(self);
+        let rank = p / 100.0 * (v.len() - 1) as f64;
+        let lower = rank.floor() as usize;
+        let upper = rank.ceil() as usize;
+        let frac = rank - lower as f64;
+        let (a, b) = (v[lower].to_f64(), v[upper].to_f64());
+        Ok(a + (b - a) * frac)
+    }
+
+    fn histogram(&self, bins: usize) -> Result<Histogram, StatsError> {
+        if bins == 0 {
+            return Err(StatsError::ZeroBins);
+        }
+        let lo = self.min_value().ok_or(StatsError::Empty)?.to_f64();
+        let hi = self.max_value().ok_or(StatsError::Empty)?.to_f64();
+
+        let mut counts = vec![0; bins];
+        for x in self {
+            let i = if hi > lo {
+                ((x.to_f64() - lo) / (hi - lo) * bins as f64) as usize
+            } else {
+                // 所有值都相等时全部放进第一个桶。
+                0
+            };
+            counts[i.min(bins - 1)] += 1;
+        }
+        Ok(Histogram { lo, hi, counts })
+    }
+
+    fn summary(&self) -> Result<Summary<T>, StatsError> {
+        let min = self.min_value().ok_or(StatsError::Empty)?;
+        let max = self.max_value().ok_or(StatsError::Empty)?;
+        Ok(Summary {
+            len: self.len(),
+            min,
+            max,
+            sum: self.sum_f64(),
+            mean: self.mean().ok_or(StatsError::Empty)?,
+            median: self.median().ok_or(StatsError::Empty)?,
+            variance: self.variance().ok_or(StatsError::Empty)?,
+        })
+    }
+}
+
+#[test]
+fn chapter_arrays() {
+    let xs = [1, 2, 3, 4, 5];
+    let ys = [0; 500];
+
+    let s = xs.summary().unwrap();
+    assert_eq!((s.len, s.min, s.max), (5, 1, 5));
+    assert_eq!((s.sum, s.mean, s.median, s.variance), (15.0, 3.0, 3.0, 2.0));
+    assert_eq!(xs.sample_variance(), Some(2.5));
+
+    let s = ys[1..4].summary().unwrap();
+    assert_eq!((s.len, s.min, s.max, s.variance), (3, 0, 0, 0.0));
+    assert_eq!(ys.mode(), Some(0));
+    assert_eq!(ys.histogram(3).unwrap().counts, vec![500, 0, 0]);
+}
+
+#[test]
+fn empty_slice_does_not_panic() {
+    let empty: [i32; 0] = [];
+
+    assert_eq!(empty.min_value(), None);
+    assert_eq!(empty.max_value(), None);
+    assert_eq!(empty.sum_f64(), 0.0);
+    assert_eq!(empty.checked_sum(), Some(0));
+    assert_eq!(empty.mean(), None);
+    assert_eq!(empty.median(), None);
+    assert_eq!(empty.mode(), None);
+    assert_eq!(empty.variance(), None);
+    assert_eq!(empty.percentile(50.0), Err(StatsError::Empty));
+    assert_eq!(empty.histogram(4), Err(StatsError::Empty));
+    assert_eq!(empty.summary(), Err(StatsError::Empty));
+    assert_eq!([1].sample_variance(), None);
+}
+
+#[test]
+fn median_mode_and_percentiles() {
+    let xs = [7, 1, 3, 3, 9, 1];
+    assert_eq!(xs.median(), Some(3.0));
+    // 1 和 3 都出现两次，取较小的。
+    assert_eq!(xs.mode(), Some(1));
+    assert_eq!(xs.percentile(0.0), Ok(1.0));
+    assert_eq!(xs.percentile(100.0), Ok(9.0));
+    // 排序后 [1, 1, 3, 3, 7, 9]，位置 0.25 * 5 = 1.25。
+    assert_eq!(xs.percentile(25.0), Ok(1.5));
+    assert_eq!(
+        xs.percentile(101.0),
+        Err(StatsError::InvalidPercentile(101.0))
+    );
+    assert!(xs.percentile(f64::NAN).is_err());
+}
+
+#[test]
+fn floats_and_histogram() {
+    let xs = [0.5f32, 2.5, -1.0, 4.0, 1.0];
+    assert_eq!(xs.min_value(), Some(-1.0));
+    assert_eq!(xs.max_value(), Some(4.0));
+    assert_eq!(xs.mean(), Some(1.4));
+    assert!((xs.variance().unwrap() - 2.94).abs() < 1e-12);
+    assert!((xs.std_dev().unwrap() - 2.94f64.sqrt()).abs() < 1e-12);
+
+    let h = xs.histogram(5).unwrap();
+    assert_eq!(h.counts, vec![1, 1, 1, 1, 1]);
+    assert_eq!(h.bin_width(), 1.0);
+    assert_eq!(h.bin_range(2), (1.0, 2.0));
+    assert_eq!(xs.histogram(0), Err(StatsError::ZeroBins));
+
+    // NaN 排在最后，不会让排序 panic。
+    let with_nan = [1.0, f64::NAN, 0.0];
+    assert_eq!(with_nan.min_value(), Some(0.0));
+    assert!(with_nan.max_value().unwrap().is_nan());
+}
+
+#[test]
+fn large_integers_are_compared_exactly() {
+    // 这些值转换成 `f64` 之后都相等。
+    let xs = [u64::MAX - 1, u64::MAX, u64::MAX - 1];
+    assert_eq!(xs.max_value(), Some(u64::MAX));
+    assert_eq!(xs.min_value(), Some(u64::MAX - 1));
+    assert_eq!(xs.mode(), Some(u64::MAX - 1));
+    assert_eq!([i64::MIN + 1, i64::MIN].min_value(), Some(i64::MIN));
+    assert_eq!([i128::MAX, i128::MAX - 1].mode(), Some(i128::MAX - 1));
+
+    assert_eq!(xs.checked_sum(), None);
+    let big = [(1 << 53) + 1, 1i64, 1];
+    assert_eq!(big.checked_sum(), Some((1 << 53) + 3));
+    assert_eq!([0.5, 0.25].checked_sum(), Some(0.75));
+    assert_eq!([0u8; 0].checked_sum(), Some(0));
+}
@@ -0,0 +1,510 @@
+/*!
+ * @Author: ZZX
+ * @Description: 符号运算
+ * 扩展 `zt_trait::fifth`：那里为 `Foo + Bar` 和 `Bar + Foo` 重载了 `+`，得到两个不同的类型。
+ * 这里的 `Var`、`Const` 和 `Expr` 之间的 `+ - * /` 以及一元的 `-` 都被重载成构造表达式树，
+ * 于是 `x * x + 3.0 * x` 就是一个可以化简、代入、求导和求值的表达式，
+ * 打印时按优先级只加必要的括号。
+ * @Date: create in 2026/10/19 5:46 上午
+ */
+
+use std::fmt::{self, Display, Formatter};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// 变量，例如 `Var::new("x")`。
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Var(String);
+
+impl Var {
+    pub fn new(name: &str) -> Self {
+        Var(name.to_owned())
+    }
+}
+
+/// 常数。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Const(pub f64);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Const(f64),
+    Var(String),
+    Neg(Box<Expr>),
+    Add(Box<Expr>, Box<Expr>),
+    Sub(Box<Expr>, Box<Expr>),
+    Mul(Box<Expr>, Box<Expr>),
+    Div(Box<Expr>, Box<Expr>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EvalError {
+    Unbound(String),
+    DivisionByZero,
+}
+
+impl Display for EvalError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            EvalError::Unbound(name) => write!(f, "variable `{}` is not bound", name),
+            EvalError::DivisionByZero => write!(f, "division by zero"),
+        }
+    }
+}
+
+impl From<Var> for Expr {
+    fn from(v: Var) -> Expr {
+        Expr::Var(v.0)
+    }
+}
+
+impl From<Const> for Expr {
+    fn from(c: Const) -> Expr {
+        Expr::Const(c.0)
+    }
+}
+
+impl From<f64> for Expr {
+    fn from(x: f64) -> Expr {
+        Expr::Const(x)
+    }
+}
+
+impl From<&Expr> for Expr {
+    fn from(e: &Expr) -> Expr {
+        e.clone()
+    }
+}
+
+impl From<&Var> for Expr {
+    fn from(v: &Var) -> Expr {
+        Expr::Var(v.0.clone())
+    }
+}
+
+fn boxed(a: impl Into<Expr>, b: impl Into<Expr>) -> (Box<Expr>, Box<Expr>) {
+    (Box::new(a.into()), Box::new(b.into()))
+}
+
+// 左边是 `Expr`、`Var`、`Const` 或者它们的引用时，右边可以是任何能转换成 `Expr` 的东西；
+// 左边是 `f64` 时只能逐个列出右边的类型。
+macro_rules! impl_ops {
+    ($($lhs:ty),*) => {$(
+        impl<R: Into<Expr>> Add<R> for $lhs {
+            type Output = Expr;
+            fn add(self, rhs: R) -> Expr {
+                let (a, b) = boxed(self, rhs);
+                Expr::Add(a, b)
+            }
+        }
+
+        impl<R: Into<Expr>> Sub<R> for $lhs {
+            type Output = Expr;
+            fn sub(self, rhs: R) -> Expr {
+                let (a, b) = boxed(self, rhs);
+                Expr::Sub(a, b)
+            }
+        }
+
+        impl<R: Into<Expr>> Mul<R> for $lhs {
+            type Output = Expr;
+            fn mul(self, rhs: R) -> Expr {
+                let (a, b) = boxed(self, rhs);
+                Expr::Mul(a, b)
+            }
+        }
+
+        impl<R: Into<Expr>> Div<R> for $lhs {
+            type Output = Expr;
+            fn div(self, rhs: R) -> Expr {
+                let (a, b) = boxed(self, rhs);
+                Expr::Div(a, b)
+            }
+        }
+
+        impl Neg for $lhs {
+            type Output = Expr;
+            fn neg(self) -> Expr {
+                Expr::Neg(Box::new(self.into()))
+            }
+        }
+    )*};
+}
+
+impl_ops!(Expr, Var, Const, &Expr, &Var);
+
+macro_rules! impl_f64_ops {
+    ($($rhs:ty),*) => {$(
+        impl Add<$rhs> for f64 {
+            type Output = Expr;
+            fn add(self, rhs: $rhs) -> Expr {
+                Const(self) + rhs
+            }
+        }
+
+        impl Sub<$rhs> for f64 {
+            type Output = Expr;
+            fn sub(self, rhs: $rhs) -> Expr {
+                Const(self) - rhs
+            }
+        }
+
+        impl Mul<$rhs> for f64 {
+            type Output = Expr;
+            fn mul(self, rhs: $rhs) -> Expr {
+                Const(self) * rhs
+            }
+        }
+
+        impl Div<$rhs> for f64 {
+            type Output = Expr;
+            fn div(self, rhs: $rhs) -> Expr {
+                Const(self) / rhs
+            }
+        }
+    )*};
+}
+
+impl_f64_ops!(Expr, Var, &Expr, &Var);
+
+impl Expr {
+    pub fn var(name: &str) -> Expr {
+        Expr::Var(name.to_owned())
+    }
+
+    fn as_const(&self) -> Option<f64> {
+        match self {
+            Expr::Const(c) => Some(*c),
+            _ => None,
+        }
+    }
+
+    fn is_const(&self, value: f64) -> bool {
+        self.as_const() == Some(value)
+    }
+
+    /// 用 `bindings` 里的值计算表达式。
+    pub fn eval(&self, bindings: &[(&str, f64)]) -> Result<f64, EvalError> {
+        Ok(match self {
+            Expr::Const(c) => *c,
+            Expr::Var(name) => bindings
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, value)| *value)
+                .ok_or_else(|| EvalError::Unbound(name.clone()))?,
+            Expr::Neg(a) => -a.eval(bindings)?,
+            Expr::Add(a, b) => a.eval(bindings)? + b.eval(bindings)?,
+            Expr::Sub(a, b) => a.eval(bindings)? - b.eval(bindings)?,
+            Expr::Mul(a, b) => a.eval(bindings)? * b.eval(bindings)?,
+            Expr::Div(a, b) => {
+                let divisor = b.eval(bindings)?;
+                if divisor == 0.0 {
+                    return Err(EvalError::DivisionByZero);
+                }
+                a.eval(bindings)? / divisor
+            }
+        })
+    }
+
+    /// 把变量 `name` 换成 `value`，不做化简。
+    pub fn subst(&self, name: &str, value: impl Into<Expr>) -> Expr {
+        self.replace(name, &value.into())
+    }
+
+    fn replace(&self, name: &str, value: &Expr) -> Expr {
+        let r = |e: &Expr| Box::new(e.replace(name, value));
+        match self {
+            Expr::Var(n) if n == name => value.clone(),
+            Expr::Const(_) | Expr::Var(_) => self.clone(),
+            Expr::Neg(a) => Expr::Neg(r(a)),
+            Expr::Add(a, b) => Expr::Add(r(a), r(b)),
+            Expr::Sub(a, b) => Expr::Sub(r(a), r(b)),
+            Expr::Mul(a, b) => Expr::Mul(r(a), r(b)),
+            Expr::Div(a, b) => Expr::Div(r(a), r(b)),
+        }
+    }
+
+    /// 对 `name` 求导，结果已经化简。
+    pub fn diff(&self, name: &str) -> Expr {
+        self.derivative(name).simplify()
+    }
+
+    fn derivative(&self, name: &str) -> Expr {
+        match self {
+            Expr::Const(_) => Expr::Const(0.0),
+            Expr::Var(n) => Expr::Const(if n == name { 1.0 } else { 0.0 }),
+            Expr::Neg(a) => -a.derivative(name),
+            Expr::Add(a, b) => a.derivative(name) + b.derivative(name),
+            Expr::Sub(a, b) => a.derivative(name) - b.derivative(name),
+            // (ab)' = a'b + ab'
+            Expr::Mul(a, b) => a.derivative(name) * &**b + &**a * b.derivative(name),
+            // (a/b)' = (a'b - ab') / (b·b)
+            Expr::Div(a, b) => {
+                (a.derivative(name) * &**b - &**a * b.derivative(name)) / (&**b * &**b)
+            }
+        }
+    }
+
+    /// 结构相同。和 `==` 不同，常数按位比较，`NaN` 等于它自己，
+    /// 所以化简到 `NaN` 之后也能停下来。
+    fn same(&self, other: &Expr) -> bool {
+        use Expr::*;
+
+        match (self, other) {
+            (Const(x), Const(y)) => x.to_bits() == y.to_bits(),
+            (Var(x), Var(y)) => x == y,
+            (Neg(a), Neg(b)) => a.same(b),
+            (Add(a, b), Add(c, d))
+            | (Sub(a, b), Sub(c, d))
+            | (Mul(a, b), Mul(c, d))
+            | (Div(a, b), Div(c, d)) => a.same(c) && b.same(d),
+            _ => false,
+        }
+    }
+
+    /// 化简到不再变化为止。规则包括常数折叠、加减零、乘除一、乘零、
+    /// 双重取负、`x - x = 0`、`x + x = 2 * x` 以及把加上负数改写成减法。
+    pub fn simplify(&self) -> Expr {
+        let mut current = self.clone();
+        loop {
+            let next = current.simplify_once();
+            if next.same(&current) {
+                return current;
+            }
+            current = next;
+        }
+    }
+
+    fn simplify_once(&self) -> Expr {
+        use Expr::*;
+
+        match self {
+            Const(_) | Var(_) => self.clone(),
+            Neg(a) => match a.simplify_once() {
+                Const(c) => Const(-c),
+                Neg(inner) => *inner,
+                a => Neg(Box::new(a)),
+            },
+            Add(a, b) => match (a.simplify_once(), b.simplify_once()) {
+                (Const(x), Const(y)) => Const(x + y),
+                (a, b) if b.is_const(0.0) => a,
+                (a, b) if a.is_const(0.0) => b,
+                (a, Neg(b)) => Sub(Box::new(a), b),
+                (a, Const(y)) if y < 0.0 => Sub(Box::new(a), Box::new(Const(-y))),
+                (a, b) if a == b => Mul(Box::new(Const(2.0)), Box::new(a)),
+                (a, b) => Add(Box::new(a), Box::new(b)),
+            },
+            Sub(a, b) => match (a.simplify_once(), b.simplify_once()) {
+                (Const(x), Const(y)) => Const(x - y),
+                (a, b) if b.is_const(0.0) => a,
+                (a, b) if a.is_const(0.0) => Neg(Box::new(b)),
+                (a, Neg(b)) => Add(Box::new(a), b),
+                (a, b) if a == b => Const(0.0),
+                (a, b) => Sub(Box::new(a), Box::new(b)),
+            },
+            Mul(a, b) => match (a.simplify_once(), b.simplify_once()) {
+                (Const(x), Const(y)) => Const(x * y),
+                (a, b) if a.is_const(0.0) || b.is_const(0.0) => Const(0.0),
+                (a, b) if b.is_const(1.0) => a,
+                (a, b) if a.is_const(1.0) => b,
+                // 常数放在前面：`x * 2` 写成 `2 * x`。
+                (a, Const(y)) => Mul(Box::new(Const(y)), Box::new(a)),
+                (a, b) => Mul(Box::new(a), Box::new(b)),
+            },
+            Div(a, b) => match (a.simplify_once(), b.simplify_once()) {
+                (Const(x), Const(y)) if y != 0.0 => Const(x / y),
+                (a, b) if b.is_const(1.0) => a,
+                (a, b) if a.is_const(0.0) && !b.is_const(0.0) => Const(0.0),
+                (a, b) => Div(Box::new(a), Box::new(b)),
+            },
+        }
+    }
+
+    /// 越大结合得越紧。
+    fn precedence(&self) -> u8 {
+        match self {
+            Expr::Add(..) | Expr::Sub(..) => 1,
+            Expr::Mul(..) | Expr::Div(..) => 2,
+            Expr::Neg(_) => 3,
+            Expr::Const(c) if *c < 0.0 => 3,
+            Expr::Const(_) | Expr::Var(_) => 4,
+        }
+    }
+}
+
+/// 需要时给 `e` 加上括号。
+fn operand(f: &mut Formatter<'_>, e: &Expr, parens: bool) -> fmt::Result {
+    if parens {
+        write!(f, "({})", e)
+    } else {
+        write!(f, "{}", e)
+    }
+}
+
+impl Display for Expr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (a, op, b) = match self {
+            Expr::Const(c) => return write!(f, "{}", c),
+            Expr::Var(name) => return write!(f, "{}", name),
+            Expr::Neg(a) => {
+                write!(f, "-")?;
+                // `-(-x)` 比 `--x` 清楚。
+                return operand(f, a, a.precedence() <= 3);
+            }
+            Expr::Add(a, b) => (a, "+", b),
+            Expr::Sub(a, b) => (a, "-", b),
+            Expr::Mul(a, b) => (a, "*", b),
+            Expr::Div(a, b) => (a, "/", b),
+        };
+
+        let p = self.precedence();
+        // 左结合：左边只有优先级更低时才要括号；右边优先级相同时，
+        // 减法和除法也要括号，`a - (b - c)` 不等于 `a - b - c`。
+        let right_parens = b.precedence() < p
+            || (b.precedence() == p && matches!(self, Expr::Sub(..) | Expr::Div(..)));
+        operand(f, a, a.precedence() < p)?;
+        write!(f, " {} ", op)?;
+        operand(f, b, right_parens)
+    }
+}
+
+impl Display for Var {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+fn xyz() -> (Var, Var, Var) {
+    (Var::new("x"), Var::new("y"), Var::new("z"))
+}
+
+#[test]
+fn operators_build_trees() {
+    let (x, y, _) = xyz();
+    assert_eq!(
+        x.clone() + Const(1.0),
+        Expr::Add(Box::new(Expr::var("x")), Box::new(Expr::Const(1.0)))
+    );
+    // 和 `Foo + Bar`、`Bar + Foo` 一样，两边的类型可以不同。
+    assert_eq!((Const(1.0) + &x).to_string(), "1 + x");
+    assert_eq!((2.0 * &x - &y / 4.0).to_string(), "2 * x - y / 4");
+    assert_eq!((-&x * 3.0).to_string(), "-x * 3");
+
+    let e = &x * &x;
+    assert_eq!((&e + &e).to_string(), "x * x + x * x");
+}
+
+#[test]
+fn minimal_parentheses() {
+    let (x, y, z) = xyz();
+    let cases = [
+        ((&x + &y) * &z, "(x + y) * z"),
+        (&x + &y * &z, "x + y * z"),
+        (&x - (&y - &z), "x - (y - z)"),
+        ((&x - &y) - &z, "x - y - z"),
+        (&x + (&y - &z), "x + y - z"),
+        (&x / (&y * &z), "x / (y * z)"),
+        (&x * (&y / &z), "x * y / z"),
+        ((&x / &y) / &z, "x / y / z"),
+        (-(&x + &y), "-(x + y)"),
+        (-(-&x), "-(-x)"),
+        (&x * -&y, "x * -y"),
+        (&x - Const(-2.0), "x - -2"),
+        (-Const(2.0) * &x, "-2 * x"),
+        (-(&x * &y), "-(x * y)"),
+    ];
+    for (expr, expected) in cases.iter() {
+        assert_eq!(expr.to_string(), *expected);
+    }
+}
+
+#[test]
+fn simplification() {
+    let (x, y, _) = xyz();
+    let cases = [
+        (&x + 0.0, "x"),
+        (0.0 * &x + &y * 1.0, "y"),
+        (Const(2.0) * Const(3.0) + &x, "6 + x"),
+        (&x - &x, "0"),
+        (&x + &x, "2 * x"),
+        (&x * 3.0, "3 * x"),
+        (-(-&x), "x"),
+        (&x + -&y, "x - y"),
+        (&x - -&y, "x + y"),
+        (&x + Const(-2.0), "x - 2"),
+        (0.0 - &x, "-x"),
+        (&x / 1.0, "x"),
+        (0.0 / &x, "0"),
+        (Const(1.0) / Const(0.0), "1 / 0"),
+    ];
+    for (expr, expected) in cases.iter() {
+        assert_eq!(
+            expr.simplify().to_string(),
+            *expected,
+            "simplifying {}",
+            expr
+        );
+    }
+}
+
+#[test]
+fn simplification_terminates_on_nan() {
+    let (x, _, _) = xyz();
+    let nan = Expr::from(Const(f64::NAN)).simplify();
+    assert!(nan.as_const().unwrap().is_nan());
+    let inf = Const(f64::INFINITY);
+    assert!((inf - inf).simplify().as_const().unwrap().is_nan());
+    assert_eq!(
+        (Const(f64::NAN) * &x + 1.0).simplify().to_string(),
+        "NaN * x + 1"
+    );
+    assert_eq!((1.0 / &x).subst("x", 0.0).simplify().to_string(), "1 / 0");
+}
+
+#[test]
+fn evaluation_and_substitution() {
+    let (x, y, _) = xyz();
+    let e = &x * &x + 3.0 * &y - 1.0;
+    assert_eq!(e.eval(&[("x", 2.0), ("y", 1.0)]), Ok(6.0));
+    assert_eq!(
+        e.eval(&[("x", 2.0)]),
+        Err(EvalError::Unbound("y".to_owned()))
+    );
+    assert_eq!(
+        (1.0 / &x).eval(&[("x", 0.0)]).unwrap_err().to_string(),
+        "division by zero"
+    );
+
+    let substituted = e.subst("y", 2.0 * &x);
+    assert_eq!(substituted.to_string(), "x * x + 3 * 2 * x - 1");
+    assert_eq!(substituted.eval(&[("x", 1.0)]), Ok(6.0));
+    assert_eq!(e.subst("x", 0.0).simplify().to_string(), "3 * y - 1");
+    assert_eq!(e.subst("z", 1.0), e);
+}
+
+#[test]
+fn differentiation() {
+    let (x, y, _) = xyz();
+    let polynomial = &x * &x + 3.0 * &x + 5.0;
+    assert_eq!(polynomial.diff("x").to_string(), "2 * x + 3");
+    assert_eq!(polynomial.diff("y").to_string(), "0");
+    assert_eq!((1.0 / &x).diff("x").to_string(), "-1 / (x * x)");
+    assert_eq!((&x * &y).diff("y").to_string(), "x");
+
+    // 和数值微分比较。
+    let f = (&x * &x * &x - 2.0 * &x) / (&x + 4.0) - -&x;
+    let df = f.diff("x");
+    let h = 1e-6;
+    for &at in &[-3.0, -1.0, 0.5, 2.0, 10.0] {
+        let numeric =
+            (f.eval(&[("x", at + h)]).unwrap() - f.eval(&[("x", at - h)]).unwrap()) / (2.0 * h);
+        let symbolic = df.eval(&[("x", at)]).unwrap();
+        assert!(
+            (numeric - symbolic).abs() < 1e-4,
+            "at {}: {} vs {}",
+            at,
+            numeric,
+            symbolic
+        );
+    }
+}
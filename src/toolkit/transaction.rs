@@ -0,0 +1,342 @@
+/*!
+ * @Author: ZZX
+ * @Description: 事务
+ * 扩展 `zt_trait::sixth`：那里的 `Droppable` 在离开作用域时打印一句话，
+ * 这里的 `Transaction` 在离开作用域时回滚。事务先把写入缓存起来，读的时候能看到自己的写入，
+ * 只有 `commit` 才会把它们交给 `Store`；没有提交就被销毁（包括 panic 展开时）的事务什么也不会留下。
+ * `savepoint` 在事务里开一个嵌套的事务，提交时并入外层，回滚时只丢掉自己的写入。
+ * @Date: create in 2026/10/19 5:46 上午
+ */
+
+use std::collections::btree_map::{self, BTreeMap};
+use std::mem;
+
+enum Write<V> {
+    Put(V),
+    Delete,
+}
+
+/// 事务的上一层：`Store` 或者外层事务。
+trait Layer<K, V> {
+    fn get(&self, key: &K) -> Option<&V>;
+    fn apply(&mut self, writes: BTreeMap<K, Write<V>>);
+    /// 直接建在这一层上的事务结束了。
+    fn finished(&mut self, committed: bool);
+}
+
+/// 内存里的键值存储，只能通过事务修改。
+pub struct Store<K, V> {
+    data: BTreeMap<K, V>,
+    commits: usize,
+    rollbacks: usize,
+}
+
+impl<K: Ord, V> Default for Store<K, V> {
+    fn default() -> Self {
+        Store {
+            data: BTreeMap::new(),
+            commits: 0,
+            rollbacks: 0,
+        }
+    }
+}
+
+impl<K: Ord, V> Store<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.data.get(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn iter(&self) -> btree_map::Iter<'_, K, V> {
+        self.data.iter()
+    }
+
+    /// 开始一个事务。事务存在期间 `Store` 被可变借用，不会有两个事务同时写。
+    pub fn begin(&mut self) -> Transaction<'_, K, V> {
+        Transaction::new(self, 0)
+    }
+
+    /// 提交了的顶层事务数。
+    pub fn commits(&self) -> usize {
+        self.commits
+    }
+
+    /// 回滚了的顶层事务数。
+    pub fn rollbacks(&self) -> usize {
+        self.rollbacks
+    }
+}
+
+impl<K: Ord, V> Layer<K, V> for Store<K, V> {
+    fn get(&self, key: &K) -> Option<&V> {
+        self.data.get(key)
+    }
+
+    fn apply(&mut self, writes: BTreeMap<K, Write<V>>) {
+        for (key, write) in writes {
+            match write {
+                Write::Put(value) => {
+                    self.data.insert(key, value);
+                }
+                Write::Delete => {
+                    self.data.remove(&key);
+                }
+            }
+        }
+    }
+
+    fn finished(&mut self, committed: bool) {
+        if committed {
+            self.commits += 1;
+        } else {
+            self.rollbacks += 1;
+        }
+    }
+}
+
+/// 缓存写入的事务，没有 `commit` 就被销毁时回滚。
+#[must_use = "dropping a transaction rolls it back"]
+pub struct Transaction<'a, K, V> {
+    parent: &'a mut dyn Layer<K, V>,
+    writes: BTreeMap<K, Write<V>>,
+    depth: usize,
+    committed: bool,
+}
+
+impl<'a, K: Ord, V> Transaction<'a, K, V> {
+    fn new(parent: &'a mut dyn Layer<K, V>, depth: usize) -> Self {
+        Transaction {
+            parent,
+            writes: BTreeMap::new(),
+            depth,
+            committed: false,
+        }
+    }
+
+    /// 顶层事务是 0，每层 `savepoint` 加一。
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// 先看自己的写入，再看外层。
+    pub fn get(&self, key: &K) -> Option<&V> {
+        match self.writes.get(key) {
+            Some(Write::Put(value)) => Some(value),
+            Some(Write::Delete) => None,
+            None => self.parent.get(key),
+        }
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    pub fn put(&mut self, key: K, value: V) {
+        self.writes.insert(key, Write::Put(value));
+    }
+
+    /// 返回删除之前 `key` 是否存在。
+    pub fn delete(&mut self, key: K) -> bool {
+        let existed = self.contains_key(&key);
+        self.writes.insert(key, Write::Delete);
+        existed
+    }
+
+    /// 缓存着的写入数，同一个键只算一次。
+    pub fn pending(&self) -> usize {
+        self.writes.len()
+    }
+
+    /// 开一个嵌套的事务。它存在期间外层事务被可变借用。
+    pub fn savepoint(&mut self) -> Transaction<'_, K, V> {
+        let depth = self.depth + 1;
+        Transaction::new(self, depth)
+    }
+
+    /// 把写入交给上一层：顶层事务写进 `Store`，保存点并入外层事务。
+    pub fn commit(mut self) {
+        let writes = mem::take(&mut self.writes);
+        self.parent.apply(writes);
+        self.committed = true;
+    }
+
+    /// 丢掉所有写入，和直接销毁一样。
+    pub fn rollback(self) {}
+}
+
+impl<K: Ord, V> Layer<K, V> for Transaction<'_, K, V> {
+    fn get(&self, key: &K) -> Option<&V> {
+        Transaction::get(self, key)
+    }
+
+    fn apply(&mut self, writes: BTreeMap<K, Write<V>>) {
+        self.writes.extend(writes);
+    }
+
+    // 保存点的结果只影响外层事务，不计数。
+    fn finished(&mut self, _committed: bool) {}
+}
+
+impl<K, V> Drop for Transaction<'_, K, V> {
+    fn drop(&mut self) {
+        // 没有提交的写入随 `writes` 一起丢掉，这就是回滚。
+        self.parent.finished(self.committed);
+    }
+}
+
+#[cfg(test)]
+use std::panic::{self, AssertUnwindSafe};
+
+#[cfg(test)]
+fn accounts() -> Store<&'static str, i64> {
+    let mut store = Store::new();
+    let mut tx = store.begin();
+    tx.put("alice", 100);
+    tx.put("bob", 50);
+    tx.commit();
+    store
+}
+
+#[cfg(test)]
+fn transfer(
+    tx: &mut Transaction<'_, &'static str, i64>,
+    from: &'static str,
+    to: &'static str,
+    amount: i64,
+) {
+    let balance = *tx.get(&from).expect("account exists");
+    tx.put(from, balance - amount);
+    assert!(balance >= amount, "insufficient funds");
+    let other = *tx.get(&to).expect("account exists");
+    tx.put(to, other + amount);
+}
+
+#[test]
+fn commit_applies_buffered_writes() {
+    let mut store = accounts();
+    assert_eq!(store.commits(), 1);
+
+    let mut tx = store.begin();
+    tx.put("carol", 7);
+    assert!(tx.delete("bob"));
+    assert!(!tx.delete("dave"));
+    // 事务里能看到自己的写入。
+    assert_eq!(tx.get(&"carol"), Some(&7));
+    assert_eq!(tx.get(&"bob"), None);
+    assert_eq!(tx.get(&"alice"), Some(&100));
+    assert_eq!(tx.pending(), 3);
+    tx.commit();
+
+    let contents: Vec<_> = store.iter().map(|(k, v)| (*k, *v)).collect();
+    assert_eq!(contents, [("alice", 100), ("carol", 7)]);
+    assert_eq!((store.commits(), store.rollbacks()), (2, 0));
+}
+
+#[test]
+fn drop_rolls_back() {
+    let mut store = accounts();
+    {
+        let mut tx = store.begin();
+        tx.put("alice", 0);
+        tx.delete("bob");
+    }
+    store.begin().rollback();
+
+    assert_eq!(store.get(&"alice"), Some(&100));
+    assert_eq!(store.get(&"bob"), Some(&50));
+    assert_eq!(store.len(), 2);
+    assert_eq!((store.commits(), store.rollbacks()), (1, 2));
+}
+
+#[test]
+fn panic_in_the_middle_rolls_back() {
+    let mut store = accounts();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let mut tx = store.begin();
+        transfer(&mut tx, "alice", "bob", 30);
+        // 这一次在扣了钱、还没加到对方账上时 panic。
+        transfer(&mut tx, "bob", "alice", 500);
+        tx.commit();
+    }));
+    assert!(result.is_err());
+
+    // 两次转账都没有发生。
+    assert_eq!(store.get(&"alice"), Some(&100));
+    assert_eq!(store.get(&"bob"), Some(&50));
+    assert_eq!(store.rollbacks(), 1);
+
+    let mut tx = store.begin();
+    transfer(&mut tx, "alice", "bob", 30);
+    tx.commit();
+    assert_eq!(store.get(&"bob"), Some(&80));
+}
+
+#[test]
+fn nested_savepoints() {
+    let mut store = accounts();
+    let mut tx = store.begin();
+    tx.put("carol", 1);
+    {
+        let mut sp = tx.savepoint();
+        assert_eq!(sp.depth(), 1);
+        sp.put("carol", 2);
+        sp.delete("alice");
+        {
+            let mut inner = sp.savepoint();
+            assert_eq!(inner.depth(), 2);
+            inner.put("dave", 4);
+            assert_eq!(inner.get(&"carol"), Some(&2));
+            assert_eq!(inner.get(&"alice"), None);
+            // 最里层回滚，`dave` 消失。
+        }
+        assert_eq!(sp.get(&"dave"), None);
+        sp.commit();
+    }
+    assert_eq!(tx.get(&"carol"), Some(&2));
+    assert_eq!(tx.get(&"alice"), None);
+    {
+        let mut sp = tx.savepoint();
+        sp.put("bob", 0);
+        sp.rollback();
+    }
+    assert_eq!(tx.get(&"bob"), Some(&50));
+
+    // 外层事务提交之前，保存点的提交都不会到达 `Store`。
+    tx.commit();
+    let contents: Vec<_> = store.iter().map(|(k, v)| (*k, *v)).collect();
+    assert_eq!(contents, [("bob", 50), ("carol", 2)]);
+    // 保存点不计数。
+    assert_eq!((store.commits(), store.rollbacks()), (2, 0));
+}
+
+#[test]
+fn panic_inside_a_savepoint() {
+    let mut store = accounts();
+    let mut tx = store.begin();
+    transfer(&mut tx, "alice", "bob", 10);
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let mut sp = tx.savepoint();
+        transfer(&mut sp, "bob", "alice", 1_000);
+        sp.commit();
+    }));
+    assert!(result.is_err());
+
+    // 只有保存点里的写入被丢掉，外层的转账还在，可以继续提交。
+    assert_eq!(tx.get(&"alice"), Some(&90));
+    assert_eq!(tx.get(&"bob"), Some(&60));
+    tx.commit();
+    assert_eq!(store.get(&"alice"), Some(&90));
+    assert_eq!(store.get(&"bob"), Some(&60));
+}
@@ -0,0 +1,311 @@
+/*!
+ * @Author: ZZX
+ * @Description: 元组工具
+ * 推广 `j_tuple::one` 里只能交换 `(i32, bool)` 的 `reverse`：
+ * 用宏为 1 到 12 元的元组生成翻转、同类型元组的 map、
+ * 在头尾压入/弹出元素、与数组互相转换以及逐元素 zip。
+ * @Date: create in 2026/10/19 5:46 上午
+ */
+
+/// 把元组倒过来：`(a, b, c)` -> `(c, b, a)`。
+pub trait TupleReverse {
+    type Output;
+    fn reverse(self) -> Self::Output;
+}
+
+/// 对所有元素类型相同的元组逐个应用同一个函数。
+pub trait TupleMap<T> {
+    type Output<U>;
+    fn map<U, F: FnMut(T) -> U>(self, f: F) -> Self::Output<U>;
+}
+
+/// 同类型元组与等长数组之间的转换。
+pub trait TupleArray<T>: Sized {
+    type Array;
+    fn into_array(self) -> Self::Array;
+    fn from_array(array: Self::Array) -> Self;
+}
+
+/// 逐元素配对：`(a, b).zip((x, y))` -> `((a, x), (b, y))`。
+pub trait TupleZip<Other> {
+    type Output;
+    fn zip(self, other: Other) -> Self::Output;
+}
+
+/// 在末尾压入一个元素，得到长一元的元组。
+pub trait PushBack<X> {
+    type Output;
+    fn push_back(self, x: X) -> Self::Output;
+}
+
+/// 在开头压入一个元素，得到长一元的元组。
+pub trait PushFront<X> {
+    type Output;
+    fn push_front(self, x: X) -> Self::Output;
+}
+
+/// 弹出第一个元素，返回 `(第一个元素, 剩下的元组)`。
+pub trait PopFront {
+    type Head;
+    type Tail;
+    fn pop_front(self) -> (Self::Head, Self::Tail);
+}
+
+/// 弹出最后一个元素，返回 `(前面的元组, 最后一个元素)`。
+pub trait PopBack {
+    type Init;
+    type Last;
+    fn pop_back(self) -> (Self::Init, Self::Last);
+}
+
+// 把标识符序列倒过来拼成元组，类型和表达式位置都能用。
+macro_rules! reversed {
+    ([$($acc:tt)*]) => { ($($acc,)*) };
+    ([$($acc:tt)*] $head:tt $($rest:tt)*) => { reversed!([$head $($acc)*] $($rest)*) };
+}
+
+// 忽略第一个参数，展开成第二个参数，用来把 `T` 重复 N 次。
+macro_rules! same {
+    ($_ignored:tt, $($replacement:tt)*) => { $($replacement)* };
+}
+
+macro_rules! impl_pop_front {
+    ($H:ident $h:ident $($T:ident $t:ident)*) => {
+        impl<$H, $($T),*> PopFront for ($H, $($T,)*) {
+            type Head = $H;
+            type Tail = ($($T,)*);
+
+            fn pop_front(self) -> ($H, ($($T,)*)) {
+                let ($h, $($t,)*) = self;
+                ($h, ($($t,)*))
+            }
+        }
+    };
+}
+
+macro_rules! impl_pop_back {
+    (@acc [$($I:ident $i:ident)*] $L:ident $l:ident) => {
+        impl<$($I,)* $L> PopBack for ($($I,)* $L,) {
+            type Init = ($($I,)*);
+            type Last = $L;
+
+            fn pop_back(self) -> (($($I,)*), $L) {
+                let ($($i,)* $l,) = self;
+                (($($i,)*), $l)
+            }
+        }
+    };
+    (@acc [$($acc:tt)*] $H:ident $h:ident $($rest:tt)+) => {
+        impl_pop_back!(@acc [$($acc)* $H $h] $($rest)+);
+    };
+}
+
+macro_rules! impl_push {
+    ($($T:ident $t:ident)*) => {
+        impl<$($T,)* X> PushBack<X> for ($($T,)*) {
+            type Output = ($($T,)* X,);
+
+            fn push_back(self, x: X) -> Self::Output {
+                let ($($t,)*) = self;
+                ($($t,)* x,)
+            }
+        }
+
+        impl<$($T,)* X> PushFront<X> for ($($T,)*) {
+            type Output = (X, $($T,)*);
+
+            fn push_front(self, x: X) -> Self::Output {
+                let ($($t,)*) = self;
+                (x, $($t,)*)
+            }
+        }
+    };
+}
+
+/// 为一个元数生成所有 trait。每组 `(T t U u)` 是本元组和 zip 对象在该位置的类型与变量名。
+macro_rules! impl_tuple {
+    ($(($T:ident $t:ident $U:ident $u:ident))+) => {
+        impl<$($T),+> TupleReverse for ($($T,)+) {
+            type Output = reversed!([] $($T)+);
+
+            fn reverse(self) -> Self::Output {
+                let ($($t,)+) = self;
+                reversed!([] $($t)+)
+            }
+        }
+
+        impl<T> TupleMap<T> for ($(same!($T, T),)+) {
+            type Output<V> = ($(same!($T, V),)+);
+
+            fn map<V, F: FnMut(T) -> V>(self, mut f: F) -> Self::Output<V> {
+                let ($($t,)+) = self;
+                // 元组表达式从左到右求值，`f` 按元素顺序调用。
+                ($(f($t),)+)
+            }
+        }
+
+        impl<T> TupleArray<T> for ($(same!($T, T),)+) {
+            type Array = [T; 0 $(+ same!($T, 1))+];
+
+            fn into_array(self) -> Self::Array {
+                let ($($t,)+) = self;
+                [$($t),+]
+            }
+
+            fn from_array(array: Self::Array) -> Self {
+                let [$($t),+] = array;
+                ($($t,)+)
+            }
+        }
+
+        impl<$($T,)+ $($U),+> TupleZip<($($U,)+)> for ($($T,)+) {
+            type Output = ($(($T, $U),)+);
+
+            fn zip(self, other: ($($U,)+)) -> Self::Output {
+                let ($($t,)+) = self;
+                let ($($u,)+) = other;
+                ($(($t, $u),)+)
+            }
+        }
+
+        impl_pop_front!($($T $t)+);
+        impl_pop_back!(@acc [] $($T $t)+);
+    };
+}
+
+impl_push!();
+impl_push!(A a);
+impl_push!(A a B b);
+impl_push!(A a B b C c);
+impl_push!(A a B b C c D d);
+impl_push!(A a B b C c D d E e);
+impl_push!(A a B b C c D d E e F f);
+impl_push!(A a B b C c D d E e F f G g);
+impl_push!(A a B b C c D d E e F f G g H h);
+impl_push!(A a B b C c D d E e F f G g H h I i);
+impl_push!(A a B b C c D d E e F f G g H h I i J j);
+impl_push!(A a B b C c D d E e F f G g H h I i J j K k);
+
+impl_tuple!((A a UA ua));
+impl_tuple!((A a UA ua)(B b UB ub));
+impl_tuple!((A a UA ua)(B b UB ub)(C c UC uc));
+impl_tuple!((A a UA ua)(B b UB ub)(C c UC uc)(D d UD ud));
+impl_tuple!((A a UA ua)(B b UB ub)(C c UC uc)(D d UD ud)(E e UE ue));
+impl_tuple!((A a UA ua)(B b UB ub)(C c UC uc)(D d UD ud)(E e UE ue)(F f UF uf));
+impl_tuple!((A a UA ua)(B b UB ub)(C c UC uc)(D d UD ud)(E e UE ue)(F f UF uf)(G g UG ug));
+impl_tuple!(
+    (A a UA ua)(B b UB ub)(C c UC uc)(D d UD ud)(E e UE ue)(F f UF uf)(G g UG ug)(H h UH uh)
+);
+impl_tuple!(
+    (A a UA ua)(B b UB ub)(C c UC uc)(D d UD ud)(E e UE ue)(F f UF uf)(G g UG ug)(H h UH uh)
+    (I i UI ui)
+);
+impl_tuple!(
+    (A a UA ua)(B b UB ub)(C c UC uc)(D d UD ud)(E e UE ue)(F f UF uf)(G g UG ug)(H h UH uh)
+    (I i UI ui)(J j UJ uj)
+);
+impl_tuple!(
+    (A a UA ua)(B b UB ub)(C c UC uc)(D d UD ud)(E e UE ue)(F f UF uf)(G g UG ug)(H h UH uh)
+    (I i UI ui)(J j UJ uj)(K k UK uk)
+);
+impl_tuple!(
+    (A a UA ua)(B b UB ub)(C c UC uc)(D d UD ud)(E e UE ue)(F f UF uf)(G g UG ug)(H h UH uh)
+    (I i UI ui)(J j UJ uj)(K k UK uk)(L l UL ul)
+);
+
+#[test]
+fn chapter_reverse() {
+    let pair = (1, true);
+    assert_eq!(pair.reverse(), (true, 1));
+
+    let long_tuple = (
+        1u8, 2u16, 3u32, 4u64, -1i8, -2i16, -3i32, -4i64, 0.1f32, 0.2f64, 'a', true,
+    );
+    assert_eq!(
+        long_tuple.reverse(),
+        (true, 'a', 0.2f64, 0.1f32, -4i64, -3i32, -2i16, -1i8, 4u64, 3u32, 2u16, 1u8)
+    );
+}
+
+#[test]
+fn push_and_pop() {
+    assert_eq!(().push_back(1), (1,));
+    assert_eq!((1, "two").push_front(0.5), (0.5, 1, "two"));
+    assert_eq!((1, "two").push_back('3'), (1, "two", '3'));
+
+    assert_eq!((1, "two", '3').pop_front(), (1, ("two", '3')));
+    assert_eq!((1, "two", '3').pop_back(), ((1, "two"), '3'));
+    assert_eq!((1,).pop_front(), (1, ()));
+    assert_eq!((1,).pop_back(), ((), 1));
+}
+
+#[test]
+fn map_zip_and_arrays() {
+    assert_eq!((1, 2, 3).map(|x| x * 10), (10, 20, 30));
+    assert_eq!(
+        (1, 2).map(|x| x.to_string()),
+        ("1".to_string(), "2".to_string())
+    );
+
+    // `map` 按顺序调用闭包，`FnMut` 可以累积状态。
+    let mut seen = Vec::new();
+    (3, 1, 2).map(|x| seen.push(x));
+    assert_eq!(seen, vec![3, 1, 2]);
+
+    assert_eq!((1, 'a').zip(("one", true)), ((1, "one"), ('a', true)));
+
+    let array: [i32; 4] = (1, 2, 3, 4).into_array();
+    assert_eq!(array, [1, 2, 3, 4]);
+    assert_eq!(<(i32, i32, i32, i32)>::from_array(array), (1, 2, 3, 4));
+}
+
+// 对每一个元数，检查所有 trait 能编译并且彼此一致。
+#[cfg(test)]
+macro_rules! check_arity {
+    ($($x:expr),+) => {{
+        let tuple = ($($x,)+);
+        let array = [$($x),+];
+        let n = array.len();
+
+        let mut reversed = array;
+        reversed.reverse();
+        assert_eq!(tuple.reverse().into_array(), reversed);
+        assert_eq!(tuple.reverse().reverse(), tuple);
+
+        assert_eq!(tuple.into_array(), array);
+        let back: ($(same!($x, i32),)+) = TupleArray::from_array(array);
+        assert_eq!(back, tuple);
+        assert_eq!(tuple.map(|x| x * 2).into_array(), array.map(|x| x * 2));
+
+        let (head, tail) = tuple.pop_front();
+        assert_eq!(head, array[0]);
+        assert_eq!(tail.push_front(head), tuple);
+
+        let (init, last) = tuple.pop_back();
+        assert_eq!(last, array[n - 1]);
+        assert_eq!(init.push_back(last), tuple);
+
+        let labels = tuple.map(|x| x.to_string());
+        let zipped = tuple.zip(labels).into_array();
+        for (i, (x, label)) in zipped.iter().enumerate() {
+            assert_eq!(*x, array[i]);
+            assert_eq!(*label, array[i].to_string());
+        }
+    }};
+}
+
+#[test]
+fn every_arity() {
+    check_arity!(1);
+    check_arity!(1, 2);
+    check_arity!(1, 2, 3);
+    check_arity!(1, 2, 3, 4);
+    check_arity!(1, 2, 3, 4, 5);
+    check_arity!(1, 2, 3, 4, 5, 6);
+    check_arity!(1, 2, 3, 4, 5, 6, 7);
+    check_arity!(1, 2, 3, 4, 5, 6, 7, 8);
+    check_arity!(1, 2, 3, 4, 5, 6, 7, 8, 9);
+    check_arity!(1, 2, 3, 4, 5, 6, 7, 8, 9, 10);
+    check_arity!(1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11);
+    check_arity!(1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12);
+}
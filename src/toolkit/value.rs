@@ -0,0 +1,588 @@
+/*!
+ * @Author: ZZX
+ * @Description: 动态值
+ * 扩展 `x_destructuring`：那里解构的元组、枚举和结构体在编译期就知道类型。
+ * `Value` 是运行时才知道结构的数据（空值、布尔、整数、浮点、字符串、列表、映射），
+ * 提供按路径查找、出错时说明位置和原因的类型化提取，
+ * 以及像结构体模式一样从 `Value` 里绑定变量的 `destructure!` 宏。
+ * @Date: create in 2026/10/19 5:46 上午
+ */
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+    List(Vec<Value>),
+    Map(BTreeMap<String, Value>),
+}
+
+/// `Value` 的种类，用在错误信息里。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Null,
+    Bool,
+    Int,
+    Float,
+    Str,
+    List,
+    Map,
+}
+
+impl Display for Kind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Kind::Null => "null",
+            Kind::Bool => "bool",
+            Kind::Int => "int",
+            Kind::Float => "float",
+            Kind::Str => "string",
+            Kind::List => "list",
+            Kind::Map => "map",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValueError {
+    /// 路径上的键或下标不存在。
+    Missing { path: String },
+    /// 路径上的某一段不是列表或映射，无法继续往下找。
+    NotContainer { path: String, found: Kind },
+    TypeMismatch {
+        path: String,
+        expected: Kind,
+        found: Kind,
+    },
+    /// 整数放不进目标类型。
+    OutOfRange {
+        path: String,
+        value: i64,
+        target: &'static str,
+    },
+}
+
+// 根路径显示为 `$`。
+fn show(path: &str) -> &str {
+    if path.is_empty() {
+        "$"
+    } else {
+        path
+    }
+}
+
+fn join(path: &str, segment: &str) -> String {
+    if path.is_empty() {
+        segment.to_string()
+    } else {
+        format!("{}.{}", path, segment)
+    }
+}
+
+impl Display for ValueError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ValueError::Missing { path } => write!(f, "`{}`: missing", show(path)),
+            ValueError::NotContainer { path, found } => {
+                write!(f, "`{}`: cannot look inside a {}", show(path), found)
+            }
+            ValueError::TypeMismatch {
+                path,
+                expected,
+                found,
+            } => write!(
+                f,
+                "`{}`: expected {}, found {}",
+                show(path),
+                expected,
+                found
+            ),
+            ValueError::OutOfRange {
+                path,
+                value,
+                target,
+            } => write!(f, "`{}`: {} does not fit in {}", show(path), value, target),
+        }
+    }
+}
+
+impl Value {
+    /// 由键值对构造一个映射。
+    pub fn map<K, V, I>(entries: I) -> Value
+    where
+        K: Into<String>,
+        V: Into<Value>,
+        I: IntoIterator<Item = (K, V)>,
+    {
+        Value::Map(
+            entries
+                .into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        )
+    }
+
+    pub fn kind(&self) -> Kind {
+        match self {
+            Value::Null => Kind::Null,
+            Value::Bool(_) => Kind::Bool,
+            Value::Int(_) => Kind::Int,
+            Value::Float(_) => Kind::Float,
+            Value::Str(_) => Kind::Str,
+            Value::List(_) => Kind::List,
+            Value::Map(_) => Kind::Map,
+        }
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+
+    /// 按路径查找，例如 `"address.city"` 或 `"tags.0"`；空路径返回自身。
+    pub fn lookup(&self, path: &str) -> Result<&Value, ValueError> {
+        let mut current = self;
+        let mut walked = String::new();
+        if path.is_empty() {
+            return Ok(current);
+        }
+
+        for segment in path.split('.') {
+            let parent = walked.clone();
+            walked = join(&walked, segment);
+            current = match current {
+                Value::Map(map) => map.get(segment),
+                Value::List(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+                other => {
+                    return Err(ValueError::NotContainer {
+                        path: parent,
+                        found: other.kind(),
+                    })
+                }
+            }
+            .ok_or_else(|| ValueError::Missing {
+                path: walked.clone(),
+            })?;
+        }
+        Ok(current)
+    }
+
+    pub fn get(&self, path: &str) -> Option<&Value> {
+        self.lookup(path).ok()
+    }
+
+    /// 查找并转换成 `T`。`T` 为 `Option<_>` 时，缺失或为空值都得到 `None`。
+    pub fn extract<T: FromValue>(&self, path: &str) -> Result<T, ValueError> {
+        match self.lookup(path) {
+            Ok(value) => T::from_value(value, path),
+            Err(ValueError::Missing { path: missing }) if missing == path => T::missing(path),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn mismatch(&self, path: &str, expected: Kind) -> ValueError {
+        ValueError::TypeMismatch {
+            path: path.to_string(),
+            expected,
+            found: self.kind(),
+        }
+    }
+
+    pub fn as_bool(&self) -> Result<bool, ValueError> {
+        bool::from_value(self, "")
+    }
+
+    pub fn as_int(&self) -> Result<i64, ValueError> {
+        i64::from_value(self, "")
+    }
+
+    /// 整数也可以当作浮点数取出。
+    pub fn as_float(&self) -> Result<f64, ValueError> {
+        f64::from_value(self, "")
+    }
+
+    pub fn as_str(&self) -> Result<&str, ValueError> {
+        match self {
+            Value::Str(s) => Ok(s),
+            other => Err(other.mismatch("", Kind::Str)),
+        }
+    }
+
+    pub fn as_list(&self) -> Result<&[Value], ValueError> {
+        match self {
+            Value::List(items) => Ok(items),
+            other => Err(other.mismatch("", Kind::List)),
+        }
+    }
+
+    pub fn as_map(&self) -> Result<&BTreeMap<String, Value>, ValueError> {
+        match self {
+            Value::Map(map) => Ok(map),
+            other => Err(other.mismatch("", Kind::Map)),
+        }
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Null => write!(f, "null"),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Int(n) => write!(f, "{}", n),
+            Value::Float(x) => write!(f, "{:?}", x),
+            Value::Str(s) => write!(f, "{:?}", s),
+            Value::List(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, "]")
+            }
+            Value::Map(map) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in map.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{:?}: {}", key, value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+macro_rules! impl_from {
+    ($($t:ty => |$x:ident| $e:expr),* $(,)?) => {
+        $(
+            impl From<$t> for Value {
+                fn from($x: $t) -> Value {
+                    $e
+                }
+            }
+        )*
+    };
+}
+
+impl_from! {
+    bool => |b| Value::Bool(b),
+    i32 => |n| Value::Int(n as i64),
+    i64 => |n| Value::Int(n),
+    f64 => |x| Value::Float(x),
+    &str => |s| Value::Str(s.to_string()),
+    String => |s| Value::Str(s),
+}
+
+impl<T: Into<Value>> From<Vec<T>> for Value {
+    fn from(items: Vec<T>) -> Value {
+        Value::List(items.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(option: Option<T>) -> Value {
+        option.map_or(Value::Null, Into::into)
+    }
+}
+
+/// 可以从 `Value` 中取出的类型。`path` 只用于错误信息。
+pub trait FromValue: Sized {
+    fn from_value(value: &Value, path: &str) -> Result<Self, ValueError>;
+
+    /// 路径上没有值时的结果，默认是 `Missing` 错误。
+    fn missing(path: &str) -> Result<Self, ValueError> {
+        Err(ValueError::Missing {
+            path: path.to_string(),
+        })
+    }
+}
+
+impl FromValue for Value {
+    fn from_value(value: &Value, _: &str) -> Result<Self, ValueError> {
+        Ok(value.clone())
+    }
+}
+
+impl FromValue for bool {
+    fn from_value(value: &Value, path: &str) -> Result<Self, ValueError> {
+        match value {
+            Value::Bool(b) => Ok(*b),
+            other => Err(other.mismatch(path, Kind::Bool)),
+        }
+    }
+}
+
+impl FromValue for i64 {
+    fn from_value(value: &Value, path: &str) -> Result<Self, ValueError> {
+        match value {
+            Value::Int(n) => Ok(*n),
+            other => Err(other.mismatch(path, Kind::Int)),
+        }
+    }
+}
+
+macro_rules! impl_from_value_int {
+    ($($t:ty),*) => {
+        $(
+            impl FromValue for $t {
+                fn from_value(value: &Value, path: &str) -> Result<Self, ValueError> {
+                    let n = i64::from_value(value, path)?;
+                    <$t as std::convert::TryFrom<i64>>::try_from(n).map_err(|_| ValueError::OutOfRange {
+                        path: path.to_string(),
+                        value: n,
+                        target: stringify!($t),
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_from_value_int!(i8, i16, i32, u8, u16, u32, u64, usize);
+
+impl FromValue for f64 {
+    fn from_value(value: &Value, path: &str) -> Result<Self, ValueError> {
+        match value {
+            Value::Float(x) => Ok(*x),
+            Value::Int(n) => Ok(*n as f64),
+            other => Err(other.mismatch(path, Kind::Float)),
+        }
+    }
+}
+
+impl FromValue for String {
+    fn from_value(value: &Value, path: &str) -> Result<Self, ValueError> {
+        match value {
+            Value::Str(s) => Ok(s.clone()),
+            other => Err(other.mismatch(path, Kind::Str)),
+        }
+    }
+}
+
+impl<T: FromValue> FromValue for Vec<T> {
+    fn from_value(value: &Value, path: &str) -> Result<Self, ValueError> {
+        match value {
+            Value::List(items) => items
+                .iter()
+                .enumerate()
+                .map(|(i, item)| T::from_value(item, &join(path, &i.to_string())))
+                .collect(),
+            other => Err(other.mismatch(path, Kind::List)),
+        }
+    }
+}
+
+impl<T: FromValue> FromValue for BTreeMap<String, T> {
+    fn from_value(value: &Value, path: &str) -> Result<Self, ValueError> {
+        match value {
+            Value::Map(map) => map
+                .iter()
+                .map(|(k, v)| Ok((k.clone(), T::from_value(v, &join(path, k))?)))
+                .collect(),
+            other => Err(other.mismatch(path, Kind::Map)),
+        }
+    }
+}
+
+impl<T: FromValue> FromValue for Option<T> {
+    fn from_value(value: &Value, path: &str) -> Result<Self, ValueError> {
+        match value {
+            Value::Null => Ok(None),
+            other => T::from_value(other, path).map(Some),
+        }
+    }
+
+    fn missing(_: &str) -> Result<Self, ValueError> {
+        Ok(None)
+    }
+}
+
+/// 像结构体模式一样从 `Value` 中绑定变量，每个变量都要写出类型：
+///
+/// ```ignore
+/// destructure!(let {
+///     name: String,
+///     age as years: u32,
+///     address: { city: String, zip: Option<String> },
+/// } = value);
+/// ```
+///
+/// 嵌套的 `{ .. }` 对应映射里的映射，`field as name` 把字段绑定到另一个名字。
+/// 没有写出的键会被忽略，相当于结构体模式末尾的 `..`。
+/// 展开后的每个 `let` 都带 `?`，所以只能在返回 `Result<_, E>` 的函数里使用，
+/// 其中 `E: From<ValueError>`。
+#[allow(unused_macros)]
+macro_rules! destructure {
+    (let { $($body:tt)* } = $value:expr) => {
+        let value: &$crate::toolkit::value::Value = &$value;
+        $crate::toolkit::value::destructure!(@fields value [] $($body)*);
+    };
+
+    (@fields $v:ident [$($prefix:ident)*]) => {};
+
+    // 嵌套的映射
+    (@fields $v:ident [$($prefix:ident)*] $field:ident : { $($inner:tt)* } $(, $($rest:tt)*)?) => {
+        $crate::toolkit::value::destructure!(@fields $v [$($prefix)* $field] $($inner)*);
+        $crate::toolkit::value::destructure!(@fields $v [$($prefix)*] $($($rest)*)?);
+    };
+
+    // 换个名字绑定
+    (@fields $v:ident [$($prefix:ident)*] $field:ident as $bind:ident : $t:ty $(, $($rest:tt)*)?) => {
+        let $bind: $t = $v.extract(concat!($(stringify!($prefix), ".",)* stringify!($field)))?;
+        $crate::toolkit::value::destructure!(@fields $v [$($prefix)*] $($($rest)*)?);
+    };
+
+    (@fields $v:ident [$($prefix:ident)*] $field:ident : $t:ty $(, $($rest:tt)*)?) => {
+        let $field: $t = $v.extract(concat!($(stringify!($prefix), ".",)* stringify!($field)))?;
+        $crate::toolkit::value::destructure!(@fields $v [$($prefix)*] $($($rest)*)?);
+    };
+}
+
+#[allow(unused_imports)]
+pub(crate) use destructure;
+
+#[cfg(test)]
+fn person() -> Value {
+    Value::map(vec![
+        ("name", Value::from("Ferris")),
+        ("age", Value::from(7)),
+        ("height", Value::from(0.3)),
+        ("tags", Value::from(vec!["crab", "rust"])),
+        ("nickname", Value::Null),
+        (
+            "address",
+            Value::map(vec![("city", "Berlin"), ("street", "Hauptstraße")]),
+        ),
+    ])
+}
+
+#[test]
+fn path_lookup() {
+    let person = person();
+    assert_eq!(person.get("address.city"), Some(&Value::from("Berlin")));
+    assert_eq!(person.get("tags.1"), Some(&Value::from("rust")));
+    assert_eq!(person.get(""), Some(&person));
+    assert_eq!(person.get("tags.2"), None);
+    assert_eq!(person.get("tags.first"), None);
+
+    assert_eq!(
+        person.lookup("address.zip"),
+        Err(ValueError::Missing {
+            path: "address.zip".to_string()
+        })
+    );
+    assert_eq!(
+        person.lookup("name.first").unwrap_err().to_string(),
+        "`name`: cannot look inside a string"
+    );
+}
+
+#[test]
+fn typed_extractors() {
+    let person = person();
+    assert_eq!(person.extract::<String>("name"), Ok("Ferris".to_string()));
+    assert_eq!(person.extract::<u8>("age"), Ok(7));
+    // 整数可以当作浮点数，反过来不行。
+    assert_eq!(person.extract::<f64>("age"), Ok(7.0));
+    assert_eq!(
+        person.extract::<i64>("height").unwrap_err().to_string(),
+        "`height`: expected int, found float"
+    );
+    assert_eq!(
+        person.extract::<Vec<String>>("tags"),
+        Ok(vec!["crab".to_string(), "rust".to_string()])
+    );
+    assert_eq!(person.extract::<Option<String>>("nickname"), Ok(None));
+    assert_eq!(person.extract::<Option<String>>("address.zip"), Ok(None));
+    // 中间的路径缺失仍然是错误。
+    assert!(person.extract::<Option<String>>("work.title").is_err());
+
+    let address: BTreeMap<String, String> = person.extract("address").unwrap();
+    assert_eq!(address["street"], "Hauptstraße");
+
+    assert_eq!(person.lookup("name").unwrap().as_str(), Ok("Ferris"));
+    assert_eq!(Value::Int(300).as_float(), Ok(300.0));
+    assert_eq!(
+        Value::Null.as_bool().unwrap_err().to_string(),
+        "`$`: expected bool, found null"
+    );
+}
+
+#[test]
+fn nested_errors_name_the_element() {
+    let value = Value::map(vec![(
+        "scores",
+        Value::List(vec![Value::from(1), Value::from(300), Value::from("x")]),
+    )]);
+
+    assert_eq!(
+        value.extract::<Vec<u8>>("scores"),
+        Err(ValueError::OutOfRange {
+            path: "scores.1".to_string(),
+            value: 300,
+            target: "u8",
+        })
+    );
+    assert_eq!(
+        value.extract::<Vec<i64>>("scores").unwrap_err().to_string(),
+        "`scores.2`: expected int, found string"
+    );
+}
+
+#[test]
+fn destructure_like_a_struct_pattern() {
+    fn describe(value: &Value) -> Result<String, ValueError> {
+        destructure!(let {
+            name: String,
+            age as years: u32,
+            tags: Vec<String>,
+            nickname: Option<String>,
+            address: { city: String, zip: Option<String> },
+        } = value);
+
+        Ok(format!(
+            "{} ({}), {} from {} {:?} {:?}",
+            name,
+            years,
+            tags.join("/"),
+            city,
+            zip,
+            nickname
+        ))
+    }
+
+    assert_eq!(
+        describe(&person()),
+        Ok("Ferris (7), crab/rust from Berlin None None".to_string())
+    );
+
+    let mut broken = person();
+    if let Value::Map(map) = &mut broken {
+        map.insert("address".to_string(), Value::map(vec![("city", 10115)]));
+    }
+    assert_eq!(
+        describe(&broken).unwrap_err().to_string(),
+        "`address.city`: expected string, found int"
+    );
+}
+
+#[test]
+fn display() {
+    let value = Value::map(vec![
+        ("a", Value::from(vec![1, 2])),
+        ("b", Value::from(Some(1.5))),
+        ("c", Value::from(None::<bool>)),
+    ]);
+    assert_eq!(
+        value.to_string(),
+        "{\"a\": [1, 2], \"b\": 1.5, \"c\": null}"
+    );
+}